@@ -0,0 +1,69 @@
+//! Measures picking throughput with 1k and 10k box frames, so that its
+//! scaling with the number of frames can be compared.
+//!
+//! Run with `cargo bench --features testing --bench picking`.
+
+use bevy::prelude::*;
+use bevy_fsl_box_frame::{testing::BoxFrameTestApp, BoxFrame};
+use std::{hint::black_box, time::Instant};
+
+/// Frames along each side of the benchmarked grids, about 1k and 10k frames.
+const GRID_SIZES: [i32; 2] = [32, 100];
+const PICKS: u32 = 1000;
+const UPDATES: u32 = 20;
+
+fn main() {
+    for grid_size in GRID_SIZES {
+        bench(grid_size);
+    }
+}
+
+/// Times picking and updates with `grid_size` by `grid_size` frames.
+fn bench(grid_size: i32) {
+    let mut test = BoxFrameTestApp::new();
+    let world = test.app.world_mut();
+    let frames: Vec<_> = (0..grid_size * grid_size)
+        .map(|i| {
+            let position = 2.0 * Vec3::new((i % grid_size) as f32, 0.0, (i / grid_size) as f32)
+                - Vec3::new(grid_size as f32, 0.0, grid_size as f32);
+            world
+                .spawn((
+                    BoxFrame::new([-0.5, -0.5, -0.5, 0.5, 0.5, 0.5]),
+                    Transform::from_translation(position),
+                ))
+                .id()
+        })
+        .collect();
+    test.set_camera_transform(Transform::from_xyz(0.0, 60.0, 80.0).looking_at(Vec3::ZERO, Vec3::Y));
+    test.hover(Vec3::new(0.0, 0.5, 0.0));
+    assert!(!test.pick().is_empty(), "the pointer should hit a frame");
+
+    let start = Instant::now();
+    for _ in 0..PICKS {
+        black_box(test.pick());
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "pick {} frames: {:?} per ray, {:.0} rays/s",
+        frames.len(),
+        elapsed / PICKS,
+        f64::from(PICKS) / elapsed.as_secs_f64()
+    );
+
+    // Moving a frame updates the broad phase during the next update.
+    let start = Instant::now();
+    for i in 0..UPDATES {
+        let world = test.app.world_mut();
+        world
+            .get_mut::<Transform>(frames[i as usize])
+            .unwrap()
+            .translation
+            .y += 0.1;
+        test.update();
+    }
+    println!(
+        "update {} frames with one changed: {:?} per update",
+        frames.len(),
+        start.elapsed() / UPDATES
+    );
+}
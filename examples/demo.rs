@@ -1,39 +1,23 @@
 use bevy::prelude::*;
-use bevy_fsl_box_frame::{BoxFrame, BoxFramePlugin, BoxFrameVisuals, SolidColorMaterial};
-use bevy_polyline::prelude::{Polyline, PolylineMaterial};
+use bevy_fsl_box_frame::{BoxFrame, BoxFramePlugin};
 
 fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
             bevy_polyline::PolylinePlugin,
-            BoxFramePlugin,
+            BoxFramePlugin::default(),
         ))
         .add_systems(Startup, setup)
         .run();
 }
 
-fn setup(
-    mut commands: Commands,
-    mut line_materials: ResMut<Assets<PolylineMaterial>>,
-    mut polylines: ResMut<Assets<Polyline>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<SolidColorMaterial>>,
-) {
-    let visuals = BoxFrameVisuals::new_default(&mut line_materials, &mut meshes, &mut materials);
-
+fn setup(mut commands: Commands) {
     // Rotate the box frame to test our surface normal calculations.
     let transform =
         Transform::from_rotation(Quat::from_axis_angle(Vec3::Y, std::f32::consts::FRAC_PI_4));
 
-    BoxFrame::build(
-        [-0.5, -0.5, -0.5, 0.5, 0.5, 0.5],
-        transform,
-        PointerButton::Primary,
-        visuals,
-        &mut polylines,
-        &mut commands.spawn(()),
-    );
+    commands.spawn((BoxFrame::new([-0.5, -0.5, -0.5, 0.5, 0.5, 0.5]), transform));
 
     commands.spawn((
         Camera3d::default(),
@@ -0,0 +1,156 @@
+use crate::{
+    box_frame::{sorted_faces, BoxFrameHandle},
+    BoxFrame,
+};
+use bevy::prelude::*;
+use bevy_polyline::prelude::{Polyline, PolylineHandle};
+
+/// Animates the faces of a [`BoxFrame`] when they are moved from code, and
+/// smooths them while they are dragged.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fsl_box_frame::{BoxFrame, BoxFrameAnimation};
+/// fn grow(mut frames: Query<&mut BoxFrame, With<BoxFrameAnimation>>) {
+///     for mut frame in &mut frames {
+///         let [x1, y1, z1, x2, y2, z2] = frame.sorted_faces();
+///         frame.move_faces([x1, y1, z1, x2 + 1.0, y2, z2]);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct BoxFrameAnimation {
+    /// How long [`BoxFrame::move_faces`] takes to move faces, in seconds.
+    ///
+    /// Also used for the rest of the way to the pointer when a smoothed drag
+    /// ends.
+    pub seconds: f32,
+    /// The easing of moved faces over time.
+    pub ease: EaseFunction,
+    /// The time constant of the low-pass filter applied to dragged faces, in
+    /// seconds, or `0.0` to follow the pointer exactly.
+    ///
+    /// Larger values reduce more jitter from noisy pointer devices, but lag
+    /// further behind the pointer.
+    pub drag_smoothing_seconds: f32,
+}
+
+impl Default for BoxFrameAnimation {
+    fn default() -> Self {
+        Self {
+            seconds: 0.25,
+            ease: EaseFunction::CubicInOut,
+            drag_smoothing_seconds: 0.0,
+        }
+    }
+}
+
+impl BoxFrameAnimation {
+    /// The factor that moves a smoothed drag towards the pointer over `delta`
+    /// seconds.
+    pub(crate) fn drag_smoothing_factor(&self, delta: f32) -> f32 {
+        if self.drag_smoothing_seconds <= 0.0 {
+            return 1.0;
+        }
+        1.0 - (-delta / self.drag_smoothing_seconds).exp()
+    }
+}
+
+/// Faces that are moving from one set of coordinates to another.
+#[derive(Clone, Copy)]
+pub(crate) struct FaceTween {
+    from: [f32; 6],
+    to: [f32; 6],
+    elapsed: f32,
+}
+
+/// Applies faces from [`BoxFrame::move_faces`] and advances their animations.
+///
+/// Faces are only moved once they aren't dragged, and dragging a face stops
+/// its frame's animation.
+pub(crate) fn animate_faces(
+    time: Res<Time>,
+    mut box_frames: Query<(&mut BoxFrame, Option<&BoxFrameAnimation>)>,
+    line_handles: Query<&PolylineHandle>,
+    mut handles: Query<(&mut BoxFrameHandle, &mut Transform)>,
+    mut polylines: ResMut<Assets<Polyline>>,
+) {
+    for (mut frame, animation) in &mut box_frames {
+        // Reading first keeps idle frames from being marked as changed.
+        if frame.requested_faces.is_none() && frame.tween.is_none() {
+            continue;
+        }
+        if frame.dragging_face.is_some() {
+            if frame.tween.is_some() {
+                frame.tween = None;
+            }
+            continue;
+        }
+
+        if let Some(target) = frame.requested_faces.take() {
+            let from = frame.sorted_faces();
+            let target = sorted_faces(target);
+            match animation.filter(|animation| animation.seconds > 0.0) {
+                Some(_) if from != target => {
+                    frame.tween = Some(FaceTween {
+                        from,
+                        to: target,
+                        elapsed: 0.0,
+                    });
+                    // The first step is taken below.
+                }
+                _ => {
+                    frame.tween = None;
+                    apply_faces(
+                        &mut frame,
+                        target,
+                        &line_handles,
+                        &mut handles,
+                        &mut polylines,
+                    );
+                    continue;
+                }
+            }
+        }
+
+        let Some(mut tween) = frame.tween else {
+            continue;
+        };
+        let Some(animation) = animation.filter(|animation| animation.seconds > 0.0) else {
+            // The animation was removed.
+            frame.tween = None;
+            apply_faces(
+                &mut frame,
+                tween.to,
+                &line_handles,
+                &mut handles,
+                &mut polylines,
+            );
+            continue;
+        };
+        tween.elapsed += time.delta_secs();
+        let progress = (tween.elapsed / animation.seconds).min(1.0);
+        let eased = animation.ease.sample_clamped(progress);
+        let faces = std::array::from_fn(|face| tween.from[face].lerp(tween.to[face], eased));
+        frame.tween = (progress < 1.0).then_some(tween);
+        apply_faces(
+            &mut frame,
+            faces,
+            &line_handles,
+            &mut handles,
+            &mut polylines,
+        );
+    }
+}
+
+fn apply_faces(
+    frame: &mut BoxFrame,
+    faces: [f32; 6],
+    line_handles: &Query<&PolylineHandle>,
+    handles: &mut Query<(&mut BoxFrameHandle, &mut Transform)>,
+    polylines: &mut Assets<Polyline>,
+) {
+    frame.set_faces(faces);
+    frame.transform_handles(handles);
+    frame.reset_lines(line_handles, polylines);
+}
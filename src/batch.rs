@@ -0,0 +1,175 @@
+use crate::{
+    box_edges, spawn_frame_children, BoxFrame, BoxFrameTheme, InteractionMode, Selected,
+    SolidColorMaterial,
+};
+use bevy::{
+    picking::hover::HoverMap,
+    platform::collections::HashSet,
+    prelude::*,
+    render::{mesh::PrimitiveTopology, render_asset::RenderAssetUsages, view::NoFrustumCulling},
+};
+use bevy_polyline::prelude::PolylineMaterial;
+
+/// The entity that draws the edges of every [`batched`](BoxFrame::batched)
+/// frame without child entities in a single draw call.
+///
+/// Created when the first batched frame is spawned.
+#[derive(Resource, Clone, Debug)]
+pub struct BoxFrameBatch {
+    /// The entity that renders [`mesh`](Self::mesh).
+    pub entity: Entity,
+    /// A line list with the 12 edges of each batched frame in world
+    /// coordinates, rebuilt when batched frames change.
+    pub mesh: Handle<Mesh>,
+    /// The material of the lines, which follows the edge color of the
+    /// [`BoxFrameTheme`].
+    pub material: Handle<SolidColorMaterial>,
+}
+
+/// Spawns the child entities of batched frames while they are hovered,
+/// selected or dragged, and despawns them afterwards.
+pub(crate) fn promote_batched_frames(
+    mut commands: Commands,
+    hover_map: Res<HoverMap>,
+    mut box_frames: Query<(Entity, &mut BoxFrame, Has<Selected>)>,
+) {
+    let hovered: HashSet<Entity> = hover_map
+        .values()
+        .flat_map(|hits| hits.keys().copied())
+        .collect();
+
+    for (entity, mut frame, selected) in &mut box_frames {
+        let promoted = !frame.batched
+            || selected
+            || frame.dragging_face.is_some()
+            || frame.dragging_slice.is_some()
+            || hovered.contains(&entity);
+        if promoted == frame.has_child_entities() {
+            continue;
+        }
+        if promoted {
+            commands.queue(move |world: &mut World| {
+                if world
+                    .get::<BoxFrame>(entity)
+                    .is_some_and(|frame| !frame.has_child_entities())
+                {
+                    spawn_frame_children(world, entity);
+                }
+            });
+        } else {
+            frame.despawn_child_entities(&mut commands);
+        }
+    }
+}
+
+/// Rebuilds the [`BoxFrameBatch`] mesh when batched frames change.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn update_box_frame_batch(
+    mut commands: Commands,
+    batch: Option<Res<BoxFrameBatch>>,
+    theme: Res<BoxFrameTheme>,
+    mut removed: RemovedComponents<BoxFrame>,
+    mut drawn_frames: Local<HashSet<Entity>>,
+    changed: Query<
+        (Entity, &BoxFrame, &InheritedVisibility),
+        Or<(
+            Changed<BoxFrame>,
+            Changed<GlobalTransform>,
+            Changed<InheritedVisibility>,
+        )>,
+    >,
+    box_frames: Query<(Entity, &BoxFrame, &GlobalTransform, &InheritedVisibility)>,
+    mut visibility: Query<&mut Visibility>,
+    line_materials: Res<Assets<PolylineMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<SolidColorMaterial>>,
+) {
+    // Only frames that are drawn by the batch, or were drawn by it before this
+    // change, affect the mesh.
+    let removed_drawn = removed
+        .read()
+        .filter(|entity| drawn_frames.contains(entity))
+        .count();
+    let frames_changed = removed_drawn > 0
+        || changed.iter().any(|(entity, frame, visibility)| {
+            is_drawn_by_batch(frame, visibility) || drawn_frames.contains(&entity)
+        });
+    let edge_color = line_materials
+        .get(&theme.0.edge_material.0)
+        .map_or(LinearRgba::WHITE, |material| material.color);
+
+    let batch = match batch {
+        Some(batch) => {
+            if !frames_changed && !theme.is_changed() {
+                return;
+            }
+            batch.clone()
+        }
+        None => {
+            if !box_frames.iter().any(|(_, frame, ..)| frame.batched) {
+                return;
+            }
+            let mesh = meshes.add(Mesh::new(
+                PrimitiveTopology::LineList,
+                RenderAssetUsages::default(),
+            ));
+            let material = materials.add(SolidColorMaterial {
+                color: edge_color,
+                alpha_mode: AlphaMode::Opaque,
+            });
+            let entity = commands
+                .spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    // The mesh is modified in place, so its bounds go stale.
+                    NoFrustumCulling,
+                ))
+                .id();
+            let batch = BoxFrameBatch {
+                entity,
+                mesh,
+                material,
+            };
+            commands.insert_resource(batch.clone());
+            batch
+        }
+    };
+
+    if let Some(material) = materials.get_mut(&batch.material) {
+        if material.color != edge_color {
+            material.color = edge_color;
+        }
+    }
+
+    drawn_frames.clear();
+    let positions: Vec<[f32; 3]> = box_frames
+        .iter()
+        .filter(|(_, frame, _, visibility)| is_drawn_by_batch(frame, visibility))
+        .inspect(|&(entity, ..)| {
+            drawn_frames.insert(entity);
+        })
+        .flat_map(|(_, frame, transform, _)| {
+            box_edges(frame.faces())
+                .into_iter()
+                .flat_map(|(corners, _)| corners)
+                .map(|corner| transform.transform_point(corner).to_array())
+        })
+        .collect();
+    if let Ok(mut visibility) = visibility.get_mut(batch.entity) {
+        visibility.set_if_neq(if positions.is_empty() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+    if let Some(mesh) = meshes.get_mut(&batch.mesh) {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    }
+}
+
+fn is_drawn_by_batch(frame: &BoxFrame, visibility: &InheritedVisibility) -> bool {
+    frame.batched
+        && !frame.has_child_entities()
+        && frame.interaction != InteractionMode::Hidden
+        && visibility.get()
+}
@@ -1,26 +1,120 @@
-use crate::{drag_face::Dragging, solid_color_material::SolidColorMaterial};
-use bevy::{color::palettes::css::RED, ecs::system::EntityCommands, math::FloatOrd, prelude::*};
+use crate::{
+    animation::FaceTween, drag_face::Dragging, slice::SliceDragging,
+    solid_color_material::SolidColorMaterial, BoxFrameHover, BoxFrameStyle, BoxFrameTheme,
+    HandleShape, HandleVisibility, Selected,
+};
+use bevy::{
+    ecs::{component::HookContext, system::EntityCommands, world::DeferredWorld},
+    math::FloatOrd,
+    prelude::*,
+};
 use bevy_polyline::prelude::{
     Polyline, PolylineBundle, PolylineHandle, PolylineMaterial, PolylineMaterialHandle,
 };
-use parry3d::{bounding_volume::Aabb, shape::Ball};
+use parry3d::bounding_volume::Aabb;
 
 /// The behavioral component of a box frame entity.
 ///
-/// Users should not manually construct this type. Instead use
-/// [`BoxFrame::build`].
+/// Inserting this component spawns child entities that render the frame's
+/// edges and handles, unless it is [`batched`](Self::batched), and removing it
+/// despawns them.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fsl_box_frame::BoxFrame;
+/// fn spawn_frame(mut commands: Commands) {
+///     commands.spawn((
+///         BoxFrame::new([-0.5, -0.5, -0.5, 0.5, 0.5, 0.5]),
+///         Transform::from_xyz(0.0, 1.0, 0.0),
+///     ));
+/// }
+/// ```
 #[derive(Component)]
+#[component(on_insert = spawn_children_hook, on_replace = despawn_children_hook)]
+#[require(
+    BoxFrameHover,
+    Transform,
+    Visibility,
+    Pickable = Pickable {
+        should_block_lower: false,
+        is_hoverable: true,
+    }
+)]
 pub struct BoxFrame {
     /// The button that triggers face dragging.
     pub drag_button: PointerButton,
     /// Assets and configuration for how the gizmo is rendered.
     pub visuals: BoxFrameVisuals,
+    /// Which pointer interactions the frame responds to.
+    pub interaction: InteractionMode,
+    /// Faces that can't be dragged, indexed by [`FaceIndex`].
+    ///
+    /// Locked faces are never highlighted and their handles are never shown.
+    pub locked_faces: [bool; 6],
+    /// When the frame's handles are shown.
+    pub handle_visibility: HandleVisibility,
+    /// Pointers within this distance of an edge, in logical pixels, pick one
+    /// of the two faces that share the edge: the one whose normal is most
+    /// perpendicular to the pointer ray, since it is the easiest to drag.
+    /// Only pointers that miss the box, or hit a face seen nearly edge-on, pick
+    /// edges.
+    ///
+    /// This makes faces of thin boxes pickable when they are viewed edge-on.
+    pub edge_pick_tolerance: f32,
+    /// Draw the frame's edges with every other batched frame in a single
+    /// [`BoxFrameBatch`](crate::BoxFrameBatch) mesh, and only spawn its child
+    /// entities while it is hovered, [`Selected`] or dragged.
+    ///
+    /// Batched frames are drawn with 1 pixel wide lines in the edge color of
+    /// the [`BoxFrameTheme`], and their handles are only shown while they have
+    /// child entities.
+    pub batched: bool,
+    /// The local coordinate of an interior section plane along each axis, or
+    /// `None` for no plane: `0` for X, `1` for Y and `2` for Z.
+    ///
+    /// Planes are drawn in the highlight material of the axis' maximum face
+    /// and can be dragged by their outline like faces, but are clamped between
+    /// the two faces along their axis.
+    pub slices: [Option<f32>; 3],
 
     pub(crate) dragging_face: Option<Dragging>,
+    pub(crate) dragging_slice: Option<SliceDragging>,
+    /// Faces from [`move_faces`](Self::move_faces) that haven't been applied.
+    pub(crate) requested_faces: Option<[f32; 6]>,
+    pub(crate) tween: Option<FaceTween>,
 
+    /// Whether `visuals` follow the [`BoxFrameTheme`].
+    pub(crate) inherit_visuals: bool,
     faces: [f32; 6],
     face_entities: [Entity; 6],
     handle_entities: [Entity; 6],
+    pub(crate) slice_entities: [Entity; 3],
+}
+
+/// Determines which pointer interactions a [`BoxFrame`] responds to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InteractionMode {
+    /// Faces are highlighted on hover and can be dragged.
+    #[default]
+    Editable,
+    /// Faces are highlighted on hover, but they can't be dragged.
+    HoverOnly,
+    /// The frame is rendered, but it ignores all pointers.
+    ReadOnly,
+    /// The frame is neither rendered nor pickable.
+    Hidden,
+}
+
+impl InteractionMode {
+    /// Whether the frame generates pointer hits.
+    pub fn is_pickable(self) -> bool {
+        matches!(self, Self::Editable | Self::HoverOnly)
+    }
+
+    /// Whether faces of the frame can be dragged.
+    pub fn is_draggable(self) -> bool {
+        self == Self::Editable
+    }
 }
 
 /// Assets and configuration for how the gizmo is rendered.
@@ -28,12 +122,24 @@ pub struct BoxFrame {
 pub struct BoxFrameVisuals {
     /// Material used for frame edges.
     pub edge_material: PolylineMaterialHandle,
+    /// Material used for the edges of a [`Selected`](crate::Selected) frame.
+    pub selected_edge_material: PolylineMaterialHandle,
     /// Material used for highlighting frame handles.
     pub edge_highlight_material: PolylineMaterialHandle,
+    /// Materials used instead of `edge_highlight_material` for highlighting
+    /// each face, indexed by [`FaceIndex`].
+    pub face_highlight_materials: Option<[PolylineMaterialHandle; 6]>,
     /// Mesh used to render a face handle.
+    ///
+    /// This should match [`handle_shape`](Self::handle_shape).
     pub handle_mesh: Handle<Mesh>,
+    /// The shape that face handles are picked against.
+    pub handle_shape: HandleShape,
     /// Material used to render a face handle.
     pub handle_material: Handle<SolidColorMaterial>,
+    /// Materials used instead of `handle_material` for the handle of each
+    /// face, indexed by [`FaceIndex`].
+    pub face_handle_materials: Option<[Handle<SolidColorMaterial>; 6]>,
     /// The scaling factor applied to a handle's [`Transform`] when there is no
     /// pointer hovering over it.
     pub handle_scale: ScalingFn,
@@ -49,19 +155,35 @@ pub struct BoxFrameVisuals {
 /// the handle's [`Transform`].
 ///
 /// The scaling factor should be considered equivalent to the perceived radius
-/// of the default handle mesh (a sphere). Note that the picking intersection
-/// test uses a unit-radius ball scaled by this function, so if you are using
-/// a custom mesh, you must account for its perceived radius, and picking might
-/// not work as well for non-spherical meshes.
+/// of the handle mesh. Picking tests the [`HandleShape`] scaled by the same
+/// factor, so custom meshes should have a radius of about 1.
 pub type ScalingFn = fn([f32; 3]) -> f32;
 
 #[derive(Component)]
 pub(crate) struct BoxFrameHandle {
     pub base_scale: f32,
     pub hover_scale: f32,
+    /// How far the handle has faded in, from `0.0` to `1.0`.
+    pub opacity: f32,
 }
 
 impl BoxFrameVisuals {
+    /// Stands in for the visuals of a frame until it is inserted.
+    fn placeholder() -> Self {
+        Self {
+            edge_material: default(),
+            selected_edge_material: default(),
+            edge_highlight_material: default(),
+            face_highlight_materials: None,
+            handle_mesh: default(),
+            handle_shape: default(),
+            handle_material: default(),
+            face_handle_materials: None,
+            handle_scale: |e| 0.05 * median3(e),
+            handle_hover_scale: 1.0,
+        }
+    }
+
     /// Creates default assets for rendering a box frame.
     ///
     /// This can be replaced by user-specified assets.
@@ -70,92 +192,169 @@ impl BoxFrameVisuals {
         meshes: &mut Assets<Mesh>,
         materials: &mut Assets<SolidColorMaterial>,
     ) -> Self {
-        Self {
-            edge_material: PolylineMaterialHandle(line_materials.add(PolylineMaterial {
-                width: 1.0,
-                ..default()
-            })),
-            edge_highlight_material: PolylineMaterialHandle(line_materials.add(PolylineMaterial {
-                width: 3.0,
-                ..default()
-            })),
+        Self::from_style(&default(), line_materials, meshes, materials)
+    }
 
-            handle_mesh: meshes.add(Sphere::new(1.0).mesh()),
-            handle_material: materials.add(RED),
-            handle_scale: |e| 0.05 * median3(e),
-            handle_hover_scale: 1.2,
+    /// Creates assets for rendering a box frame in the given `style`.
+    pub fn from_style(
+        style: &BoxFrameStyle,
+        line_materials: &mut Assets<PolylineMaterial>,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<SolidColorMaterial>,
+    ) -> Self {
+        let mut line_material = |color: Color, width: f32| {
+            PolylineMaterialHandle(line_materials.add(PolylineMaterial {
+                width,
+                color: color.to_linear(),
+                ..default()
+            }))
+        };
+        Self {
+            edge_material: line_material(style.edge_color, style.edge_width),
+            selected_edge_material: line_material(
+                style.selected_edge_color,
+                style.selected_edge_width,
+            ),
+            edge_highlight_material: line_material(style.highlight_color, style.highlight_width),
+            face_highlight_materials: style.axis_colors.map(|colors| {
+                per_face(colors.map(|color| line_material(color, style.highlight_width)))
+            }),
+
+            handle_mesh: meshes.add(style.handle_shape.mesh()),
+            handle_shape: style.handle_shape.clone(),
+            handle_material: materials.add(style.handle_color.to_srgba()),
+            face_handle_materials: style
+                .axis_colors
+                .map(|colors| per_face(colors.map(|color| materials.add(color.to_srgba())))),
+            handle_scale: style.handle_scale,
+            handle_hover_scale: style.handle_hover_scale,
         }
     }
+
+    /// The material used for highlighting `face`.
+    pub fn highlight_material(&self, face: FaceIndex) -> &PolylineMaterialHandle {
+        self.face_highlight_materials
+            .as_ref()
+            .map_or(&self.edge_highlight_material, |materials| &materials[face])
+    }
+
+    /// The material used for the handle of `face`.
+    pub fn handle_material(&self, face: FaceIndex) -> &Handle<SolidColorMaterial> {
+        self.face_handle_materials
+            .as_ref()
+            .map_or(&self.handle_material, |materials| &materials[face])
+    }
 }
 
 impl BoxFrame {
+    /// A frame with the given face coordinates that is dragged by the primary
+    /// button and rendered with the [`BoxFrameTheme`].
+    ///
+    /// `faces`: Coordinates of each face along it's normal axis. See
+    /// [`FaceIndex`].
+    pub fn new(faces: [f32; 6]) -> Self {
+        Self {
+            faces: sorted_faces(faces),
+            face_entities: [Entity::PLACEHOLDER; 6],
+            handle_entities: [Entity::PLACEHOLDER; 6],
+            slice_entities: [Entity::PLACEHOLDER; 3],
+            drag_button: PointerButton::Primary,
+            visuals: BoxFrameVisuals::placeholder(),
+            inherit_visuals: true,
+            interaction: InteractionMode::default(),
+            locked_faces: [false; 6],
+            handle_visibility: HandleVisibility::default(),
+            edge_pick_tolerance: 5.0,
+            batched: false,
+            slices: [None; 3],
+            dragging_face: None,
+            dragging_slice: None,
+            requested_faces: None,
+            tween: None,
+        }
+    }
+
+    /// Sets the button that triggers face dragging.
+    pub fn with_drag_button(mut self, drag_button: PointerButton) -> Self {
+        self.drag_button = drag_button;
+        self
+    }
+
+    /// Renders this frame with `visuals` instead of the [`BoxFrameTheme`].
+    pub fn with_visuals(mut self, visuals: BoxFrameVisuals) -> Self {
+        self.visuals = visuals;
+        self.inherit_visuals = false;
+        self
+    }
+
+    /// Sets which pointer interactions the frame responds to.
+    pub fn with_interaction(mut self, interaction: InteractionMode) -> Self {
+        self.interaction = interaction;
+        self
+    }
+
+    /// Sets when the frame's handles are shown.
+    pub fn with_handle_visibility(mut self, handle_visibility: HandleVisibility) -> Self {
+        self.handle_visibility = handle_visibility;
+        self
+    }
+
+    /// Sets the [`edge_pick_tolerance`](Self::edge_pick_tolerance) in logical
+    /// pixels.
+    pub fn with_edge_pick_tolerance(mut self, pixels: f32) -> Self {
+        self.edge_pick_tolerance = pixels;
+        self
+    }
+
+    /// Sets whether the frame is [`batched`](Self::batched).
+    pub fn with_batched(mut self, batched: bool) -> Self {
+        self.batched = batched;
+        self
+    }
+
+    /// Adds an interior section plane at `coord` along `axis`. See
+    /// [`slices`](Self::slices).
+    pub fn with_slice(mut self, axis: usize, coord: f32) -> Self {
+        self.slices[axis] = Some(coord);
+        self
+    }
+
     /// Uses `commands` to build a box frame entity.
     ///
     /// `faces`: Coordinates of each face along it's normal axis. See
     /// [`FaceIndex`].
+    #[deprecated(note = "spawn `BoxFrame::new(faces)` instead")]
     pub fn build(
         faces: [f32; 6],
         transform: Transform,
         drag_button: PointerButton,
         visuals: BoxFrameVisuals,
-        polylines: &mut Assets<Polyline>,
+        _polylines: &mut Assets<Polyline>,
         commands: &mut EntityCommands,
     ) {
-        let faces = sorted_faces(faces);
-        let extents = box_extents(faces);
-        let base_scale = (visuals.handle_scale)(extents);
-        let mut face_entities = [Entity::PLACEHOLDER; 6];
-        let mut handle_entities = [Entity::PLACEHOLDER; 6];
-        commands
-            .with_children(|builder| {
-                for (face, entity) in face_polylines(faces).into_iter().zip(&mut face_entities) {
-                    *entity = builder
-                        .spawn(PolylineBundle {
-                            polyline: PolylineHandle(polylines.add(face)),
-                            material: visuals.edge_material.clone(),
-                            ..default()
-                        })
-                        .id();
-                }
-                for (handle_center, entity) in
-                    face_centers(faces).into_iter().zip(&mut handle_entities)
-                {
-                    *entity = builder
-                        .spawn((
-                            Mesh3d(visuals.handle_mesh.clone()),
-                            MeshMaterial3d(visuals.handle_material.clone()),
-                            Transform::default()
-                                .with_translation(handle_center)
-                                .with_scale(Vec3::splat((visuals.handle_scale)(extents))),
-                            Visibility::Hidden,
-                            BoxFrameHandle {
-                                base_scale,
-                                hover_scale: visuals.handle_hover_scale,
-                            },
-                            Pickable {
-                                should_block_lower: false,
-                                is_hoverable: true,
-                            },
-                        ))
-                        .id();
-                }
-            })
-            .insert((
-                Self {
-                    faces,
-                    face_entities,
-                    handle_entities,
-                    drag_button,
-                    visuals,
-                    dragging_face: None,
-                },
-                transform,
-                Visibility::default(),
-                Pickable {
-                    should_block_lower: false,
-                    is_hoverable: true,
-                },
-            ));
+        commands.insert((
+            Self::new(faces)
+                .with_drag_button(drag_button)
+                .with_visuals(visuals),
+            transform,
+        ));
+    }
+
+    /// A frame with the same faces and settings that spawns its own child
+    /// entities and isn't being dragged.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self {
+            drag_button: self.drag_button,
+            visuals: self.visuals.clone(),
+            interaction: self.interaction,
+            locked_faces: self.locked_faces,
+            handle_visibility: self.handle_visibility,
+            edge_pick_tolerance: self.edge_pick_tolerance,
+            batched: self.batched,
+            slices: self.slices,
+            inherit_visuals: self.inherit_visuals,
+            ..Self::new(self.sorted_faces())
+        }
     }
 
     /// The coordinates of each face. See [`FaceIndex`].
@@ -175,6 +374,41 @@ impl BoxFrame {
         sorted_faces(self.faces)
     }
 
+    /// Moves the faces to `faces`, in local coordinates, on the next update.
+    ///
+    /// With a [`BoxFrameAnimation`](crate::BoxFrameAnimation), the faces move
+    /// smoothly over its duration instead. Faces aren't moved while they are
+    /// dragged, but once the drag ends.
+    pub fn move_faces(&mut self, faces: [f32; 6]) {
+        self.requested_faces = Some(faces);
+    }
+
+    /// Whether faces are still moving towards faces from
+    /// [`move_faces`](Self::move_faces).
+    pub fn is_animating(&self) -> bool {
+        self.requested_faces.is_some() || self.tween.is_some()
+    }
+
+    /// Prevents `face` from being dragged.
+    pub fn lock_face(&mut self, face: FaceIndex) {
+        self.locked_faces[face] = true;
+    }
+
+    /// Allows `face` to be dragged again.
+    pub fn unlock_face(&mut self, face: FaceIndex) {
+        self.locked_faces[face] = false;
+    }
+
+    /// Whether `face` is prevented from being dragged.
+    pub fn is_face_locked(&self, face: FaceIndex) -> bool {
+        self.locked_faces[face]
+    }
+
+    /// Whether `face` can currently start a drag.
+    pub(crate) fn can_drag_face(&self, face: FaceIndex) -> bool {
+        self.interaction.is_draggable() && !self.is_face_locked(face)
+    }
+
     /// The center of the box's AABB in local coordinates.
     pub fn center(&self) -> Vec3 {
         self.aabb().center().into()
@@ -193,24 +427,73 @@ impl BoxFrame {
         face_centers(self.faces)
     }
 
-    pub(crate) fn handle_ball(&self) -> Ball {
-        let radius = (self.visuals.handle_scale)(self.extents());
-        Ball::new(radius)
-    }
-
+    /// Indexed by [`FaceIndex`].
     pub(crate) fn handle_entities(&self) -> [Entity; 6] {
         self.handle_entities
     }
 
+    pub(crate) fn face_entities(&self) -> [Entity; 6] {
+        self.face_entities
+    }
+
+    /// Whether the edge and handle entities of the frame exist.
+    pub(crate) fn has_child_entities(&self) -> bool {
+        self.face_entities[0] != Entity::PLACEHOLDER
+    }
+
+    /// Despawns the edge, handle and slice entities of the frame.
+    pub(crate) fn despawn_child_entities(&mut self, commands: &mut Commands) {
+        for child in self
+            .face_entities
+            .into_iter()
+            .chain(self.handle_entities)
+            .chain(self.slice_entities)
+        {
+            if let Ok(mut child) = commands.get_entity(child) {
+                child.try_despawn();
+            }
+        }
+        self.face_entities = [Entity::PLACEHOLDER; 6];
+        self.handle_entities = [Entity::PLACEHOLDER; 6];
+        self.slice_entities = [Entity::PLACEHOLDER; 3];
+    }
+
+    pub(crate) fn set_faces(&mut self, faces: [f32; 6]) {
+        self.faces = sorted_faces(faces);
+    }
+
     pub(crate) fn set_face_during_drag(&mut self, face: usize, coord: f32) {
         // NOTE: We aren't sorting the faces until the drag ends, because this
         // allows them to pass through each other.
         self.faces[face] = coord;
     }
 
+    /// The material of an edge that isn't highlighted.
+    pub(crate) fn edge_material(&self, selected: bool) -> &PolylineMaterialHandle {
+        if selected {
+            &self.visuals.selected_edge_material
+        } else {
+            &self.visuals.edge_material
+        }
+    }
+
+    /// Moves a smoothed face that is behind the pointer the rest of the way
+    /// once its drag ends.
+    pub(crate) fn settle_drag(&mut self) {
+        let Some(dragging) = self.dragging_face else {
+            return;
+        };
+        let face = dragging.face();
+        if let Some(target) = dragging.target_coord.filter(|&t| t != self.faces[face]) {
+            let mut faces = self.faces;
+            faces[face] = target;
+            self.move_faces(faces);
+        }
+    }
+
     pub(crate) fn on_drag_end(
         &mut self,
-        line_handles: &mut Query<&mut PolylineHandle>,
+        line_handles: &Query<&PolylineHandle>,
         polylines: &mut Assets<Polyline>,
     ) {
         self.dragging_face = None;
@@ -236,27 +519,42 @@ impl BoxFrame {
         }
     }
 
+    /// Updates the polylines of faces whose edges moved.
+    ///
+    /// Each frame owns its polyline assets, so they are modified in place.
     pub(crate) fn reset_lines(
         &self,
-        line_handles: &mut Query<&mut PolylineHandle>,
+        line_handles: &Query<&PolylineHandle>,
         polylines: &mut Assets<Polyline>,
     ) {
         let new_lines = face_polylines(self.faces);
         for (face_entity, new_line) in self.face_entities.into_iter().zip(new_lines) {
-            let Ok(mut line_handle) = line_handles.get_mut(face_entity) else {
+            let Ok(line_handle) = line_handles.get(face_entity) else {
                 continue;
             };
-            *line_handle = PolylineHandle(polylines.add(new_line));
+            // Only mutable access marks the asset as modified, which uploads
+            // it to the GPU again.
+            if polylines
+                .get(&line_handle.0)
+                .is_some_and(|line| line.vertices == new_line.vertices)
+            {
+                continue;
+            }
+            if let Some(line) = polylines.get_mut(&line_handle.0) {
+                *line = new_line;
+            }
         }
     }
 
     pub(crate) fn clear_highlights(
         &self,
+        selected: bool,
         material_handles: &mut Query<&mut PolylineMaterialHandle>,
     ) {
+        let edge_material = self.edge_material(selected);
         for face_entity in self.face_entities {
             if let Ok(mut line_handle) = material_handles.get_mut(face_entity) {
-                *line_handle = self.visuals.edge_material.clone();
+                *line_handle = edge_material.clone();
             }
         }
     }
@@ -268,11 +566,106 @@ impl BoxFrame {
     ) {
         // Highlight the picked face.
         if let Ok(mut line_handle) = line_handles.get_mut(self.face_entities[face]) {
-            *line_handle = self.visuals.edge_highlight_material.clone();
+            *line_handle = self.visuals.highlight_material(face).clone();
         }
     }
 }
 
+fn spawn_children_hook(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let default_visuals = world.get_resource::<BoxFrameTheme>().map(|t| t.0.clone());
+    let Some(mut frame) = world.get_mut::<BoxFrame>(entity) else {
+        return;
+    };
+    if let Some(visuals) = default_visuals.filter(|_| frame.inherit_visuals) {
+        frame.visuals = visuals;
+    }
+    // Batched frames spawn their children when they are promoted.
+    if frame.batched {
+        return;
+    }
+    world
+        .commands()
+        .queue(move |world: &mut World| spawn_frame_children(world, entity));
+}
+
+fn despawn_children_hook(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(frame) = world.get::<BoxFrame>(entity) else {
+        return;
+    };
+    let children: Vec<_> = frame
+        .face_entities
+        .into_iter()
+        .chain(frame.handle_entities)
+        .chain(frame.slice_entities)
+        .filter(|&e| e != Entity::PLACEHOLDER)
+        .collect();
+    let mut commands = world.commands();
+    for child in children {
+        if let Ok(mut child) = commands.get_entity(child) {
+            child.try_despawn();
+        }
+    }
+}
+
+/// Spawns the edge and handle entities of the frame on `entity`.
+pub(crate) fn spawn_frame_children(world: &mut World, entity: Entity) {
+    let Some(frame) = world.get::<BoxFrame>(entity) else {
+        return;
+    };
+    let selected = world.get::<Selected>(entity).is_some();
+    let faces = frame.faces;
+    let visuals = frame.visuals.clone();
+    let edge_material = frame.edge_material(selected).clone();
+    let extents = box_extents(faces);
+    let base_scale = (visuals.handle_scale)(extents);
+
+    let Some(mut polylines) = world.get_resource_mut::<Assets<Polyline>>() else {
+        return;
+    };
+    let lines = face_polylines(faces).map(|line| polylines.add(line));
+
+    let face_entities = lines.map(|line| {
+        world
+            .spawn((
+                PolylineBundle {
+                    polyline: PolylineHandle(line),
+                    material: edge_material.clone(),
+                    ..default()
+                },
+                ChildOf(entity),
+            ))
+            .id()
+    });
+    let handle_centers = face_centers(faces);
+    let handle_entities = [0, 1, 2, 3, 4, 5].map(|face| {
+        world
+            .spawn((
+                Mesh3d(visuals.handle_mesh.clone()),
+                MeshMaterial3d(visuals.handle_material(face).clone()),
+                Transform::default()
+                    .with_translation(handle_centers[face])
+                    .with_rotation(Quat::from_rotation_arc(Vec3::Y, FACE_NORMALS[face]))
+                    .with_scale(Vec3::splat(base_scale)),
+                Visibility::Hidden,
+                BoxFrameHandle {
+                    base_scale,
+                    hover_scale: visuals.handle_hover_scale,
+                    opacity: 0.0,
+                },
+                Pickable {
+                    should_block_lower: false,
+                    is_hoverable: true,
+                },
+                ChildOf(entity),
+            ))
+            .id()
+    });
+
+    let mut frame = world.get_mut::<BoxFrame>(entity).unwrap();
+    frame.face_entities = face_entities;
+    frame.handle_entities = handle_entities;
+}
+
 /// ```text
 /// 0 = -X
 /// 1 = -Y
@@ -290,7 +683,7 @@ const POS_X: FaceIndex = 3;
 const POS_Y: FaceIndex = 4;
 const POS_Z: FaceIndex = 5;
 
-const FACE_NORMALS: [Vec3; 6] = [
+pub(crate) const FACE_NORMALS: [Vec3; 6] = [
     Vec3::NEG_X,
     Vec3::NEG_Y,
     Vec3::NEG_Z,
@@ -323,7 +716,7 @@ const CUBE_CORNERS: [[FaceIndex; 3]; 8] = [
 ];
 
 /// Indexed by [`FaceIndex`].
-const FACE_QUADS: [[CornerIndex; 4]; 6] = [
+pub(crate) const FACE_QUADS: [[CornerIndex; 4]; 6] = [
     [0b000, 0b010, 0b110, 0b100], // -X
     [0b000, 0b100, 0b101, 0b001], // -Y
     [0b000, 0b001, 0b011, 0b010], // -Z
@@ -332,7 +725,27 @@ const FACE_QUADS: [[CornerIndex; 4]; 6] = [
     [0b100, 0b110, 0b111, 0b101], // +Z
 ];
 
-fn sorted_faces(faces: [f32; 6]) -> [f32; 6] {
+/// Indexed by edge. The corners at either end of each edge, and the two faces
+/// that share it.
+const BOX_EDGES: [([CornerIndex; 2], [FaceIndex; 2]); 12] = [
+    // Along X.
+    ([0b000, 0b001], [NEG_Y, NEG_Z]),
+    ([0b010, 0b011], [POS_Y, NEG_Z]),
+    ([0b100, 0b101], [NEG_Y, POS_Z]),
+    ([0b110, 0b111], [POS_Y, POS_Z]),
+    // Along Y.
+    ([0b000, 0b010], [NEG_X, NEG_Z]),
+    ([0b001, 0b011], [POS_X, NEG_Z]),
+    ([0b100, 0b110], [NEG_X, POS_Z]),
+    ([0b101, 0b111], [POS_X, POS_Z]),
+    // Along Z.
+    ([0b000, 0b100], [NEG_X, NEG_Y]),
+    ([0b001, 0b101], [POS_X, NEG_Y]),
+    ([0b010, 0b110], [NEG_X, POS_Y]),
+    ([0b011, 0b111], [POS_X, POS_Y]),
+];
+
+pub(crate) fn sorted_faces(faces: [f32; 6]) -> [f32; 6] {
     let [x1, y1, z1, x2, y2, z2] = faces;
     [
         x1.min(x2),
@@ -344,20 +757,21 @@ fn sorted_faces(faces: [f32; 6]) -> [f32; 6] {
     ]
 }
 
-fn aabb_from_faces(faces: [f32; 6]) -> Aabb {
+pub(crate) fn aabb_from_faces(faces: [f32; 6]) -> Aabb {
     let [x1, y1, z1, x2, y2, z2] = sorted_faces(faces);
     Aabb::new([x1, y1, z1].into(), [x2, y2, z2].into())
 }
 
-fn face_centers(faces: [f32; 6]) -> [Vec3; 6] {
+/// Indexed by [`FaceIndex`].
+pub(crate) fn face_centers(faces: [f32; 6]) -> [Vec3; 6] {
     let [x1, y1, z1, x2, y2, z2] = sorted_faces(faces);
     let c = 0.5 * Vec3::new(x1 + x2, y1 + y2, z1 + z2);
     [
         Vec3::new(x1, c.y, c.z),
-        Vec3::new(x2, c.y, c.z),
         Vec3::new(c.x, y1, c.z),
-        Vec3::new(c.x, y2, c.z),
         Vec3::new(c.x, c.y, z1),
+        Vec3::new(x2, c.y, c.z),
+        Vec3::new(c.x, y2, c.z),
         Vec3::new(c.x, c.y, z2),
     ]
 }
@@ -367,16 +781,27 @@ fn box_extents(faces: [f32; 6]) -> [f32; 3] {
     [(x2 - x1).abs(), (y2 - y1).abs(), (z2 - z1).abs()]
 }
 
+/// Repeats a value for each axis on both faces along the axis.
+fn per_face<T: Clone>([x, y, z]: [T; 3]) -> [T; 6] {
+    [x.clone(), y.clone(), z.clone(), x, y, z]
+}
+
 /// The median of three values.
 pub fn median3(mut extents: [f32; 3]) -> f32 {
     extents.sort_unstable_by_key(|&x| FloatOrd(x));
     extents[1]
 }
 
-fn corner_vertices(faces: [f32; 6]) -> [Vec3; 8] {
+pub(crate) fn corner_vertices(faces: [f32; 6]) -> [Vec3; 8] {
     CUBE_CORNERS.map(|[x, y, z]| Vec3::new(faces[x], faces[y], faces[z]))
 }
 
+/// The end points of each edge, and the two faces that share it.
+pub(crate) fn box_edges(faces: [f32; 6]) -> [([Vec3; 2], [FaceIndex; 2]); 12] {
+    let verts = corner_vertices(faces);
+    BOX_EDGES.map(|(corners, faces)| (corners.map(|corner| verts[corner]), faces))
+}
+
 /// A polyline of 4 edges for each face.
 fn face_polylines(faces: [f32; 6]) -> [Polyline; 6] {
     let verts = corner_vertices(faces);
@@ -0,0 +1,183 @@
+use crate::BoxFrame;
+use bevy::{
+    ecs::prelude::*,
+    platform::collections::HashMap,
+    prelude::{Camera, GlobalTransform, Ray3d, Vec2, Vec3},
+};
+use parry3d::{
+    bounding_volume::{Aabb, BoundingVolume, SimdAabb},
+    math::{SimdBool, SIMD_WIDTH},
+    partitioning::{Qbvh, QbvhUpdateWorkspace, SimdVisitStatus},
+    query::{Ray, RayCast},
+};
+
+/// A bounding volume hierarchy over the world-space bounds of every
+/// [`BoxFrame`], including its handles, so the picking backend only tests the
+/// frames near each ray.
+///
+/// Leaves are keyed by [`Entity::index`] and updated incrementally when frames
+/// are added, changed, moved or removed.
+#[derive(Resource, Default)]
+pub(crate) struct BoxFrameBroadPhase {
+    qbvh: Qbvh<u32>,
+    workspace: QbvhUpdateWorkspace,
+    leaves: HashMap<u32, Leaf>,
+    /// The largest [`BoxFrame::edge_pick_tolerance`] of any frame.
+    max_edge_pick_tolerance: f32,
+}
+
+struct Leaf {
+    entity: Entity,
+    aabb: Aabb,
+    edge_pick_tolerance: f32,
+}
+
+impl BoxFrameBroadPhase {
+    /// The frames whose bounds `ray` passes through, or passes within the
+    /// edge picking tolerance of on screen.
+    pub(crate) fn candidates(
+        &self,
+        ray: Ray3d,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Vec<Entity> {
+        let (offset, spread) = self.tolerance_cone(ray, camera, camera_transform);
+        let parry_ray = Ray::new(ray.origin.into(), ray.direction.as_vec3().into());
+        let origin = ray.origin;
+
+        let mut candidates = Vec::new();
+        let mut visitor = |bv: &SimdAabb, data: Option<[Option<&u32>; SIMD_WIDTH]>| {
+            let mut mask = [false; SIMD_WIDTH];
+            for (lane, hit) in mask.iter_mut().enumerate() {
+                let aabb = bv.extract(lane);
+                if aabb.mins.x > aabb.maxs.x {
+                    continue;
+                }
+                // A point of the box is at most this far from the ray origin,
+                // so its screen-space tolerance covers at most this distance
+                // from the ray.
+                let mins = Vec3::from(aabb.mins) - origin;
+                let maxs = Vec3::from(aabb.maxs) - origin;
+                let farthest = mins.abs().max(maxs.abs()).length();
+                let margin = offset + spread * (farthest + offset);
+                *hit = aabb
+                    .loosened(margin)
+                    .intersects_local_ray(&parry_ray, f32::MAX);
+            }
+            if let Some(data) = data {
+                for (index, _) in data.iter().zip(mask).filter(|(_, hit)| *hit) {
+                    if let Some(leaf) = index.and_then(|index| self.leaves.get(index)) {
+                        candidates.push(leaf.entity);
+                    }
+                }
+            }
+            SimdVisitStatus::MaybeContinue(SimdBool::from(mask))
+        };
+        self.qbvh.traverse_depth_first(&mut visitor);
+        candidates
+    }
+
+    /// How far, in world units, the rays through the pixels within the
+    /// maximum edge picking tolerance of `ray` stray from it: by at most
+    /// `offset + spread * t` at a distance `t` along the ray.
+    fn tolerance_cone(
+        &self,
+        ray: Ray3d,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> (f32, f32) {
+        let tolerance = self.max_edge_pick_tolerance;
+        if tolerance <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let Ok(pointer) = camera.world_to_viewport(camera_transform, ray.get_point(1.0)) else {
+            return (0.0, 0.0);
+        };
+        // The corners of a square around the tolerance circle.
+        [
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.0, 1.0),
+        ]
+        .into_iter()
+        .filter_map(|corner| {
+            camera
+                .viewport_to_world(camera_transform, pointer + tolerance * corner)
+                .ok()
+        })
+        .fold((0.0, 0.0), |(offset, spread), corner_ray| {
+            (
+                f32::max(offset, corner_ray.origin.distance(ray.origin)),
+                f32::max(spread, corner_ray.direction.distance(*ray.direction)),
+            )
+        })
+    }
+}
+
+/// Updates the [`BoxFrameBroadPhase`] with the frames that were added, changed,
+/// moved or removed since the last update.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_broad_phase(
+    mut broad_phase: ResMut<BoxFrameBroadPhase>,
+    mut removed: RemovedComponents<BoxFrame>,
+    box_frames: Query<
+        (Entity, &BoxFrame, &GlobalTransform),
+        Or<(Changed<BoxFrame>, Changed<GlobalTransform>)>,
+    >,
+) {
+    let broad_phase = &mut *broad_phase;
+    let mut changed = false;
+    for entity in removed.read() {
+        if broad_phase
+            .leaves
+            .get(&entity.index())
+            .is_some_and(|leaf| leaf.entity == entity)
+        {
+            broad_phase.leaves.remove(&entity.index());
+            broad_phase.qbvh.remove(entity.index());
+            changed = true;
+        }
+    }
+    for (entity, frame, transform) in &box_frames {
+        broad_phase.leaves.insert(
+            entity.index(),
+            Leaf {
+                entity,
+                aabb: world_bounds(frame, transform),
+                edge_pick_tolerance: frame.edge_pick_tolerance,
+            },
+        );
+        broad_phase.qbvh.pre_update_or_insert(entity.index());
+        changed = true;
+    }
+    if !changed {
+        return;
+    }
+
+    let leaves = &broad_phase.leaves;
+    broad_phase
+        .qbvh
+        .refit(0.0, &mut broad_phase.workspace, |index| leaves[index].aabb);
+    broad_phase.qbvh.rebalance(0.0, &mut broad_phase.workspace);
+    broad_phase.max_edge_pick_tolerance = leaves
+        .values()
+        .map(|leaf| leaf.edge_pick_tolerance)
+        .fold(0.0, f32::max);
+}
+
+/// The world-space AABB of `frame` and its handles at their hovered size.
+fn world_bounds(frame: &BoxFrame, transform: &GlobalTransform) -> Aabb {
+    let visuals = &frame.visuals;
+    let handle_radius = visuals.handle_shape.bounding_radius()
+        * (visuals.handle_scale)(frame.extents())
+        * visuals.handle_hover_scale.max(1.0);
+    let local = frame.aabb().loosened(handle_radius);
+    let affine = transform.affine();
+    let points: Vec<_> = local
+        .vertices()
+        .iter()
+        .map(|&vertex| affine.transform_point3(vertex.into()).into())
+        .collect();
+    Aabb::from_points(&points)
+}
@@ -0,0 +1,152 @@
+use crate::{aabb_from_faces, picking_backend::isometry_from_transform, BoxFrame, FaceIndex};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use parry3d::{
+    na::{Isometry3, Translation3},
+    query::intersection_test,
+    shape::Cuboid,
+};
+
+/// Prevents frames in the same collision group from overlapping.
+///
+/// A dragged face stops where its frame makes contact with another frame in
+/// the same group. Frames that already overlap when the drag starts don't block
+/// each other. Frames without this component never collide.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoxFrameCollisionGroup(pub u32);
+
+/// Sent on each update in which a dragged face was stopped by another frame.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoxFrameBlocked {
+    /// The frame whose face was dragged.
+    pub frame: Entity,
+    /// The face that was dragged.
+    pub face: FaceIndex,
+    /// The frame that stopped the dragged face.
+    pub blocker: Entity,
+}
+
+/// The world-space OBB of a frame in a collision group.
+pub(crate) struct Collider {
+    entity: Entity,
+    group: u32,
+    isometry: Isometry3<f32>,
+    cuboid: Cuboid,
+}
+
+impl Collider {
+    fn new(entity: Entity, group: u32, faces: [f32; 6], transform: &GlobalTransform) -> Self {
+        let aabb = aabb_from_faces(faces);
+        // Shrinking the box lets frames touch without overlapping, e.g. after
+        // snapping one frame to another.
+        let half_extents = aabb
+            .half_extents()
+            .add_scalar(-CONTACT_TOLERANCE)
+            .sup(&[0.0; 3].into());
+        let center = Translation3::from(aabb.center().coords);
+        Self {
+            entity,
+            group,
+            isometry: isometry_from_transform(transform) * center,
+            cuboid: Cuboid::new(half_extents),
+        }
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        intersection_test(&self.isometry, &self.cuboid, &other.isometry, &other.cuboid)
+            .unwrap_or(false)
+    }
+}
+
+/// How far frames may overlap while still being considered in contact.
+const CONTACT_TOLERANCE: f32 = 1e-4;
+
+/// Iterations of the binary search for the point of contact.
+const CONTACT_SEARCH_ITERATIONS: usize = 24;
+
+/// Clamps dragged faces at contact with other frames.
+#[derive(SystemParam)]
+pub(crate) struct OverlapConstraint<'w, 's> {
+    groups: Query<'w, 's, &'static BoxFrameCollisionGroup>,
+    blocked_events: EventWriter<'w, BoxFrameBlocked>,
+}
+
+impl OverlapConstraint<'_, '_> {
+    /// The colliders of all frames that belong to a collision group.
+    pub fn colliders<'a>(
+        &self,
+        frames: impl Iterator<Item = (Entity, &'a BoxFrame, &'a GlobalTransform)>,
+    ) -> Vec<Collider> {
+        if self.groups.is_empty() {
+            return Vec::new();
+        }
+        frames
+            .filter_map(|(entity, frame, transform)| {
+                let group = self.groups.get(entity).ok()?;
+                Some(Collider::new(entity, group.0, frame.faces(), transform))
+            })
+            .collect()
+    }
+
+    /// Returns the coordinate closest to `coord` that `face` can be dragged to
+    /// without pushing `frame` into another frame.
+    ///
+    /// Frames that overlapped `frame` while `face` was at `initial_coord` don't
+    /// block it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn clamp(
+        &mut self,
+        colliders: &[Collider],
+        entity: Entity,
+        frame: &BoxFrame,
+        transform: &GlobalTransform,
+        face: FaceIndex,
+        initial_coord: f32,
+        coord: f32,
+    ) -> f32 {
+        let Ok(&BoxFrameCollisionGroup(group)) = self.groups.get(entity) else {
+            return coord;
+        };
+        let start_coord = frame.faces()[face];
+        let collider_at = |c: f32| {
+            let mut faces = frame.faces();
+            faces[face] = c;
+            Collider::new(entity, group, faces, transform)
+        };
+
+        let initial = collider_at(initial_coord);
+        let blockers: Vec<_> = colliders
+            .iter()
+            .filter(|c| c.entity != entity && c.group == group && !initial.intersects(c))
+            .collect();
+        let blocker_at = |c: f32| {
+            let collider = collider_at(c);
+            blockers
+                .iter()
+                .find(|b| collider.intersects(b))
+                .map(|b| b.entity)
+        };
+        let Some(mut blocker) = blocker_at(coord) else {
+            return coord;
+        };
+
+        // Moving a single face sweeps the box monotonically, so the contact
+        // point is where the box first intersects a blocker.
+        let (mut free, mut blocked) = (0.0, 1.0);
+        for _ in 0..CONTACT_SEARCH_ITERATIONS {
+            let mid = 0.5 * (free + blocked);
+            if let Some(b) = blocker_at(start_coord.lerp(coord, mid)) {
+                blocked = mid;
+                blocker = b;
+            } else {
+                free = mid;
+            }
+        }
+
+        self.blocked_events.write(BoxFrameBlocked {
+            frame: entity,
+            face,
+            blocker,
+        });
+        start_coord.lerp(coord, free)
+    }
+}
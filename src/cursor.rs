@@ -0,0 +1,122 @@
+use crate::{
+    selection::selection_allows_editing, BoxFrame, BoxFrameHover, BoxFrameSelection, Selected,
+    FACE_NORMALS,
+};
+use bevy::{
+    picking::pointer::{PointerId, PointerLocation},
+    platform::collections::HashMap,
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    window::SystemCursorIcon,
+    winit::cursor::CursorIcon,
+};
+
+/// Changes the cursor icon of a window while a pointer in it hovers over or
+/// drags a face of a [`BoxFrame`].
+///
+/// The icon is a resize cursor oriented along the face's normal on screen.
+/// The window's previous [`CursorIcon`] is restored afterwards.
+///
+/// Requires the `cursor` feature and
+/// [`BoxFramePlugin`](crate::BoxFramePlugin).
+pub struct BoxFrameCursorPlugin;
+
+impl Plugin for BoxFrameCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorOverrides>().add_systems(
+            Update,
+            update_cursor_icons.after(crate::highlight::highlight_face),
+        );
+    }
+}
+
+/// The icons of windows before their cursor was overridden.
+#[derive(Resource, Default)]
+struct CursorOverrides(HashMap<Entity, Option<CursorIcon>>);
+
+#[allow(clippy::too_many_arguments)]
+fn update_cursor_icons(
+    mut commands: Commands,
+    mut overrides: ResMut<CursorOverrides>,
+    selection: Option<Res<BoxFrameSelection>>,
+    box_frames: Query<(&BoxFrame, &BoxFrameHover, &GlobalTransform, Has<Selected>)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cursors: Query<&CursorIcon>,
+) {
+    let mut icons = HashMap::<Entity, SystemCursorIcon>::default();
+    for (frame, hover, transform, selected) in &box_frames {
+        let Some(hovered) = hover.0 else {
+            continue;
+        };
+        let draggable = frame.dragging_face.is_some()
+            || (selection_allows_editing(selection.as_deref(), selected)
+                && frame.can_drag_face(hovered.face));
+        if !draggable {
+            continue;
+        }
+        let Some(window) = pointers
+            .iter()
+            .find(|(id, _)| **id == hovered.pointer)
+            .and_then(|(_, location)| match location.location()?.target {
+                NormalizedRenderTarget::Window(window) => Some(window.entity()),
+                _ => None,
+            })
+        else {
+            continue;
+        };
+        let Ok((camera, camera_transform)) = cameras.get(hovered.camera) else {
+            continue;
+        };
+        let normal = transform.rotation() * FACE_NORMALS[hovered.face];
+        let to_screen = |p: Vec3| camera.world_to_viewport(camera_transform, p).ok();
+        let (Some(start), Some(end)) = (
+            to_screen(hovered.position),
+            to_screen(hovered.position + normal),
+        ) else {
+            continue;
+        };
+        icons.insert(window, resize_icon(end - start));
+    }
+
+    for (&window, &icon) in &icons {
+        let current = cursors.get(window).ok();
+        overrides
+            .0
+            .entry(window)
+            .or_insert_with(|| current.cloned());
+        let icon = CursorIcon::System(icon);
+        if current != Some(&icon) {
+            commands.entity(window).insert(icon);
+        }
+    }
+    overrides.0.retain(|&window, previous| {
+        if icons.contains_key(&window) {
+            return true;
+        }
+        if let Ok(mut window) = commands.get_entity(window) {
+            match previous.take() {
+                Some(icon) => window.insert(icon),
+                None => window.remove::<CursorIcon>(),
+            };
+        }
+        false
+    });
+}
+
+/// The resize cursor closest to `direction` in viewport coordinates, where
+/// `+Y` points down.
+fn resize_icon(direction: Vec2) -> SystemCursorIcon {
+    // The angle of the axis in [0, 180) degrees, clockwise from the +X axis.
+    let angle = direction
+        .y
+        .atan2(direction.x)
+        .to_degrees()
+        .rem_euclid(180.0);
+    match angle {
+        a if !(22.5..157.5).contains(&a) => SystemCursorIcon::EwResize,
+        a if a < 67.5 => SystemCursorIcon::NwseResize,
+        a if a < 112.5 => SystemCursorIcon::NsResize,
+        _ => SystemCursorIcon::NeswResize,
+    }
+}
@@ -1,12 +1,22 @@
-use crate::{face_index_from_world_normal, face_sign, BoxFrame, BoxFrameHandle, FaceIndex};
+use crate::{
+    collision::OverlapConstraint,
+    face_index_from_world_normal, face_sign,
+    selection::selection_allows_editing,
+    snapping::{FaceSnapper, FrameSnapshot},
+    BoxFrame, BoxFrameAnimation, BoxFrameGroup, BoxFrameHandle, BoxFrameSelection, FaceIndex,
+    GroupDragMode, InteractionMode, Selected,
+};
 use approx::relative_eq;
 use bevy::{
     picking::backend::ray::{RayId, RayMap},
+    platform::collections::HashMap,
     prelude::*,
 };
-use bevy_polyline::prelude::{Polyline, PolylineHandle};
+use bevy_polyline::prelude::{Polyline, PolylineHandle, PolylineMaterialHandle};
 
-// This data is constant while dragging is occurring.
+// This data is constant while dragging is occurring, except for the target of
+// smoothed drags.
+#[derive(Clone, Copy)]
 pub(crate) struct Dragging {
     // The ray that started dragging.
     ray_id: RayId,
@@ -17,29 +27,55 @@ pub(crate) struct Dragging {
     // The ray along which the face is translated during dragging. In world
     // coordinates.
     drag_ray: Ray3d,
+    // Set when this frame follows a face dragged on another frame in its
+    // group. The drag ray belongs to the other frame.
+    follow: Option<GroupDragMode>,
+    // Where the pointer puts the face when the drag is smoothed by a
+    // `BoxFrameAnimation`.
+    pub(crate) target_coord: Option<f32>,
 }
 
 impl Dragging {
     pub fn face(&self) -> FaceIndex {
         self.face
     }
+
+    pub fn ray_id(&self) -> RayId {
+        self.ray_id
+    }
 }
 
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub(crate) fn drag_face(
     mut drag_start_events: EventReader<Pointer<DragStart>>,
     mut drag_end_events: EventReader<Pointer<DragEnd>>,
     ray_map: Res<RayMap>,
+    selection: Option<Res<BoxFrameSelection>>,
     mut polylines: ResMut<Assets<Polyline>>,
-    mut box_frames: Query<(&mut BoxFrame, &GlobalTransform)>,
-    mut line_handles: Query<&mut PolylineHandle>,
+    mut box_frames: Query<(
+        Entity,
+        &mut BoxFrame,
+        &GlobalTransform,
+        Has<Selected>,
+        Option<&BoxFrameGroup>,
+    )>,
+    line_handles: Query<&PolylineHandle>,
+    mut line_materials: Query<&mut PolylineMaterialHandle>,
     mut handles: Query<(&mut BoxFrameHandle, &mut Transform)>,
+    animations: Query<&BoxFrameAnimation>,
+    time: Res<Time>,
+    mut snapper: FaceSnapper,
+    mut overlap: OverlapConstraint,
 ) {
     // Start or stop the dragging state machine based on events.
     for drag_start in drag_start_events.read() {
-        let Ok((mut frame, transform)) = box_frames.get_mut(drag_start.target) else {
+        let Ok((_, mut frame, transform, selected, group)) = box_frames.get_mut(drag_start.target)
+        else {
             continue;
         };
+        if !selection_allows_editing(selection.as_deref(), selected) {
+            continue;
+        }
         if drag_start.event.button != frame.drag_button {
             continue;
         }
@@ -49,6 +85,9 @@ pub(crate) fn drag_face(
             continue;
         };
         let face = face_index_from_world_normal(world_normal, transform);
+        if !frame.can_drag_face(face) {
+            continue;
+        }
         let maybe_direction = Dir3::new(world_normal).ok();
 
         // The world normal Vec3 could be zero, making it impossible to determine the direction.
@@ -61,50 +100,198 @@ pub(crate) fn drag_face(
                 origin: world_position,
                 direction,
             },
+            follow: None,
+            target_coord: None,
         });
+        let Some(dragging) = frame.dragging_face else {
+            continue;
+        };
+
+        // Other frames in the same group follow this drag.
+        let group = group.copied();
+        let selection_mode = selection
+            .as_ref()
+            .and_then(|s| s.group_edit)
+            .filter(|_| selected);
+        if group.is_none() && selection_mode.is_none() {
+            continue;
+        }
+        for (entity, mut follower, _, follower_selected, follower_group) in &mut box_frames {
+            if entity == drag_start.target || follower.dragging_face.is_some() {
+                continue;
+            }
+            let mode = if let Some(group) =
+                group.filter(|g| follower_group.is_some_and(|fg| fg.id == g.id))
+            {
+                group.mode
+            } else if let Some(mode) = selection_mode.filter(|_| follower_selected) {
+                mode
+            } else {
+                continue;
+            };
+            if !selection_allows_editing(selection.as_deref(), follower_selected)
+                || !follower.can_drag_face(face)
+            {
+                continue;
+            }
+            follower.dragging_face = Some(Dragging {
+                initial_coord: follower.faces()[face],
+                follow: Some(mode),
+                target_coord: None,
+                ..dragging
+            });
+        }
     }
     for drag_end in drag_end_events.read() {
-        let Ok((mut frame, _)) = box_frames.get_mut(drag_end.target) else {
+        let Ok((_, mut frame, ..)) = box_frames.get_mut(drag_end.target) else {
             continue;
         };
-        frame.on_drag_end(&mut line_handles, &mut polylines);
+        frame.settle_drag();
+        frame.on_drag_end(&line_handles, &mut polylines);
     }
 
+    // The frame may have been locked or deselected after the drag started.
+    for (_, mut frame, _, selected, _) in box_frames.iter_mut() {
+        if let Some(dragging) = &frame.dragging_face {
+            if !frame.can_drag_face(dragging.face())
+                || !selection_allows_editing(selection.as_deref(), selected)
+            {
+                frame.on_drag_end(&line_handles, &mut polylines);
+            }
+        }
+    }
+
+    // Followers don't receive their own drag events, so they stop once their
+    // leader stops for any reason, including being despawned.
+    let leader_rays: Vec<_> = box_frames
+        .iter()
+        .filter_map(|(_, frame, ..)| frame.dragging_face)
+        .filter(|d| d.follow.is_none())
+        .map(|d| d.ray_id)
+        .collect();
+    for (_, mut follower, _, selected, _) in box_frames.iter_mut() {
+        if follower
+            .dragging_face
+            .is_some_and(|d| d.follow.is_some() && !leader_rays.contains(&d.ray_id))
+        {
+            follower.settle_drag();
+            follower.on_drag_end(&line_handles, &mut polylines);
+            follower.clear_highlights(selected, &mut line_materials);
+        }
+    }
+
+    snapper.clear();
+    if !box_frames
+        .iter()
+        .any(|(_, frame, ..)| frame.dragging_face.is_some())
+    {
+        return;
+    }
+    let snapshots: Vec<_> = if snapper.is_enabled() {
+        box_frames
+            .iter()
+            .filter(|(_, frame, ..)| frame.interaction != InteractionMode::Hidden)
+            .map(|(entity, frame, transform, ..)| FrameSnapshot::new(entity, frame, transform))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let colliders = overlap.colliders(
+        box_frames
+            .iter()
+            .map(|(entity, frame, transform, ..)| (entity, frame, transform)),
+    );
+
     // For all frames currently in the "dragging" state, we need to calculate
     // the new desired position of the face being dragged and update the box
-    // frame to reflect that.
-    for (mut frame, _) in box_frames.iter_mut() {
-        let Some(Dragging {
-            ray_id,
-            face,
-            initial_coord,
-            drag_ray,
-        }) = frame.dragging_face
-        else {
-            continue;
-        };
+    // frame to reflect that. Frames that are dragged directly go first, so
+    // their followers can reuse the (possibly snapped or blocked) drag
+    // distance.
+    let mut leader_deltas = HashMap::<RayId, f32>::default();
+    for update_followers in [false, true] {
+        for (entity, mut frame, transform, _, _) in box_frames.iter_mut() {
+            let Some(Dragging {
+                ray_id,
+                face,
+                initial_coord,
+                drag_ray,
+                follow,
+                target_coord,
+            }) = frame.dragging_face
+            else {
+                continue;
+            };
+            if follow.is_some() != update_followers {
+                continue;
+            }
 
-        let Some(pointer_ray) = &ray_map.map.get(&ray_id) else {
-            continue;
-        };
+            let drag_delta = if let Some(&delta) = follow.and(leader_deltas.get(&ray_id)) {
+                delta
+            } else {
+                let Some(pointer_ray) = &ray_map.map.get(&ray_id) else {
+                    continue;
+                };
 
-        // Determine the new face coordinates based on the desired position of
-        // the dragging face.
-        let Some((drag_delta, _)) = closest_points_on_two_rays(&drag_ray, pointer_ray) else {
-            continue;
-        };
+                // Determine the new face coordinates based on the desired
+                // position of the dragging face.
+                let Some((drag_delta, _)) = closest_points_on_two_rays(&drag_ray, pointer_ray)
+                else {
+                    continue;
+                };
+                snapper.snap(entity, ray_id, drag_ray, drag_delta, &snapshots)
+            };
 
-        // NOTE: Assumes drag_ray is a unit vector.
-        frame.set_face_during_drag(face, initial_coord + face_sign(face) * drag_delta);
-        frame.transform_handles(&mut handles);
-        frame.reset_lines(&mut line_handles, &mut polylines)
+            let coord = match follow {
+                // NOTE: Assumes drag_ray is a unit vector.
+                None | Some(GroupDragMode::Delta) => initial_coord + face_sign(face) * drag_delta,
+                Some(GroupDragMode::Coordinate) => {
+                    let world_point = drag_ray.get_point(drag_delta);
+                    let local_point = transform.affine().inverse().transform_point3(world_point);
+                    local_point[face % 3]
+                }
+            };
+            let mut coord = overlap.clamp(
+                &colliders,
+                entity,
+                &frame,
+                transform,
+                face,
+                initial_coord,
+                coord,
+            );
+            if follow.is_none() {
+                // Followers move as far as the leader could, not as far as its
+                // pointer asked for.
+                leader_deltas
+                    .entry(ray_id)
+                    .or_insert(face_sign(face) * (coord - initial_coord));
+            }
+            let smoothing = animations
+                .get(entity)
+                .map_or(1.0, |a| a.drag_smoothing_factor(time.delta_secs()));
+            if smoothing < 1.0 {
+                if target_coord != Some(coord) {
+                    if let Some(dragging) = &mut frame.dragging_face {
+                        dragging.target_coord = Some(coord);
+                    }
+                }
+                let current = frame.faces()[face];
+                coord = current + (coord - current) * smoothing;
+            }
+            if coord == frame.faces()[face] {
+                continue;
+            }
+            frame.set_face_during_drag(face, coord);
+            frame.transform_handles(&mut handles);
+            frame.reset_lines(&line_handles, &mut polylines)
+        }
     }
 }
 
 /// Find the closest pair of points `(p1, p2)` where `p1` is on ray `r1` and
 /// `p2` is on ray `r2`. Returns `(t1, t2)` such that `p_n =
 /// r_n.get_point(t_n)`.
-fn closest_points_on_two_rays(r1: &Ray3d, r2: &Ray3d) -> Option<(f32, f32)> {
+pub(crate) fn closest_points_on_two_rays(r1: &Ray3d, r2: &Ray3d) -> Option<(f32, f32)> {
     // If the rays are parallel, then there are infinitely many solutions.
     if vectors_are_parallel(*r1.direction, *r2.direction) {
         return None;
@@ -0,0 +1,276 @@
+use crate::{
+    box_frame::face_sign,
+    split::{merge_frames, split_frame},
+    BoxFrame, BoxFrameSplit, FaceIndex, PreciseFaces,
+};
+use bevy::{ecs::system::EntityCommands, prelude::*};
+
+/// Commands that copy a [`BoxFrame`] entity into new, fully interactive
+/// frames, split it into parts, or merge frames back together.
+///
+/// Copies and parts share the source's visuals and settings, [`Transform`]
+/// rotation and scale, parent and [`PreciseFaces`], and are offset along the
+/// source's local axes. They aren't [`Selected`](crate::Selected), grouped or
+/// dragged. If the entity isn't a frame when the commands are applied, the
+/// copies are despawned.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fsl_box_frame::{BoxFrameArray, BoxFrameCommandsExt};
+/// fn build_wall(mut commands: Commands, source: Single<Entity, With<Name>>) {
+///     // 4 x 3 frames, including the source, stacked along +X and +Y.
+///     commands
+///         .entity(*source)
+///         .array_box_frame(BoxFrameArray::grid(UVec3::new(4, 3, 1)));
+/// }
+/// ```
+pub trait BoxFrameCommandsExt {
+    /// Copies the frame in place, or next to the given face when `adjacent_to`
+    /// is `Some`, and returns the copy.
+    fn duplicate_box_frame(&mut self, adjacent_to: Option<FaceIndex>) -> Entity;
+
+    /// Mirrors the frame across one of its own faces and returns the copy.
+    ///
+    /// The copy is adjacent to `face`, face settings along its axis, like
+    /// [`locked_faces`](BoxFrame::locked_faces), are swapped, and the section
+    /// plane along its axis is reflected.
+    fn mirror_box_frame(&mut self, face: FaceIndex) -> Entity;
+
+    /// Copies the frame into an [`BoxFrameArray`] and returns the copies,
+    /// ordered by their cell along X, then Y, then Z.
+    fn array_box_frame(&mut self, array: BoxFrameArray) -> Vec<Entity>;
+
+    /// Splits the frame into the parts of `split`. The frame becomes the first
+    /// part, and the others are spawned.
+    ///
+    /// Section planes are only kept by the parts they pass through. A plane on
+    /// the boundary between two parts goes to the part on its maximum side.
+    ///
+    /// Sends [`BoxFrameDivided`](crate::BoxFrameDivided) with every part.
+    fn split_box_frame(&mut self, split: BoxFrameSplit) -> &mut Self;
+
+    /// Merges `others` into this frame, which grows to cover them, and
+    /// despawns them.
+    ///
+    /// The frames must share a parent and local axes, and fill a box without
+    /// overlapping, e.g. parts of a split frame. Otherwise nothing changes.
+    /// Sends [`BoxFramesMerged`](crate::BoxFramesMerged) on success.
+    ///
+    /// The frame keeps its own section planes, and takes each one it lacks
+    /// from the first of `others` that has a plane along that axis.
+    fn merge_box_frames(&mut self, others: impl IntoIterator<Item = Entity>) -> &mut Self;
+}
+
+/// A linear or grid array of copies of a frame, each offset by the frame's
+/// extents plus a gap along its local axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxFrameArray {
+    /// The number of cells along each local axis, including the source
+    /// frame's cell.
+    pub counts: UVec3,
+    /// The space between neighboring frames along each local axis.
+    pub gap: Vec3,
+    /// Whether cells are placed towards the minimum face along each axis,
+    /// instead of the maximum face.
+    pub negative: BVec3,
+}
+
+impl BoxFrameArray {
+    /// `copies` frames in a row next to `face`.
+    pub fn linear(face: FaceIndex, copies: u32) -> Self {
+        let axis = face % 3;
+        let mut counts = UVec3::ONE;
+        counts[axis] = copies + 1;
+        let mut negative = BVec3::FALSE;
+        negative.set(axis, face_sign(face) < 0.0);
+        Self {
+            counts,
+            gap: Vec3::ZERO,
+            negative,
+        }
+    }
+
+    /// A grid of `counts` cells, including the source frame's cell, that
+    /// grows towards the maximum face along each axis.
+    pub fn grid(counts: UVec3) -> Self {
+        Self {
+            counts,
+            gap: Vec3::ZERO,
+            negative: BVec3::FALSE,
+        }
+    }
+
+    /// Sets the [`gap`](Self::gap) between neighboring frames.
+    pub fn with_gap(mut self, gap: Vec3) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// The cell of each copy, skipping the source frame's cell.
+    fn cells(&self) -> impl Iterator<Item = UVec3> + '_ {
+        (0..self.counts.z)
+            .flat_map(move |z| (0..self.counts.y).map(move |y| (y, z)))
+            .flat_map(move |(y, z)| (0..self.counts.x).map(move |x| UVec3::new(x, y, z)))
+            .filter(|&cell| cell != UVec3::ZERO)
+    }
+}
+
+impl BoxFrameCommandsExt for EntityCommands<'_> {
+    fn duplicate_box_frame(&mut self, adjacent_to: Option<FaceIndex>) -> Entity {
+        let copy = self.commands().spawn_empty().id();
+        self.queue(move |source: EntityWorldMut| {
+            let offset = adjacent_to.map_or(Vec3::ZERO, |face| {
+                source
+                    .get::<BoxFrame>()
+                    .map_or(Vec3::ZERO, |frame| adjacent_offset(frame, face))
+            });
+            copy_frame(source, copy, offset, None);
+        });
+        copy
+    }
+
+    fn mirror_box_frame(&mut self, face: FaceIndex) -> Entity {
+        let copy = self.commands().spawn_empty().id();
+        self.queue(move |source: EntityWorldMut| {
+            let offset = source
+                .get::<BoxFrame>()
+                .map_or(Vec3::ZERO, |frame| adjacent_offset(frame, face));
+            copy_frame(source, copy, offset, Some(face % 3));
+        });
+        copy
+    }
+
+    fn array_box_frame(&mut self, array: BoxFrameArray) -> Vec<Entity> {
+        let copies: Vec<_> = array
+            .cells()
+            .map(|_| self.commands().spawn_empty().id())
+            .collect();
+        let copies_to_place = copies.clone();
+        self.queue(move |mut source: EntityWorldMut| {
+            let extents = source
+                .get::<BoxFrame>()
+                .map_or(Vec3::ZERO, |frame| Vec3::from(frame.extents()));
+            let step = Vec3::select(array.negative, -Vec3::ONE, Vec3::ONE) * (extents + array.gap);
+            for (cell, copy) in array.cells().zip(copies_to_place) {
+                source = copy_frame(source, copy, step * cell.as_vec3(), None);
+            }
+        });
+        copies
+    }
+
+    fn split_box_frame(&mut self, split: BoxFrameSplit) -> &mut Self {
+        self.queue(move |source: EntityWorldMut| split_frame(source, &split))
+    }
+
+    fn merge_box_frames(&mut self, others: impl IntoIterator<Item = Entity>) -> &mut Self {
+        let others: Vec<_> = others.into_iter().collect();
+        self.queue(move |target: EntityWorldMut| merge_frames(target, &others))
+    }
+}
+
+/// The offset from `frame` to the frame of the same size next to `face`.
+fn adjacent_offset(frame: &BoxFrame, face: FaceIndex) -> Vec3 {
+    let axis = face % 3;
+    let mut offset = Vec3::ZERO;
+    offset[axis] = face_sign(face) * frame.extents()[axis];
+    offset
+}
+
+/// Turns `copy` into a copy of the frame on `source`, offset by `offset` along
+/// its local axes and optionally mirrored along `mirror_axis`.
+fn copy_frame(
+    mut source: EntityWorldMut,
+    copy: Entity,
+    offset: Vec3,
+    mirror_axis: Option<usize>,
+) -> EntityWorldMut {
+    let Some(mut frame_copy) = FrameCopy::of(&source) else {
+        source.world_scope(|world| {
+            if let Ok(copy) = world.get_entity_mut(copy) {
+                copy.despawn();
+            }
+        });
+        return source;
+    };
+    if let Some(axis) = mirror_axis {
+        let faces = frame_copy.frame.sorted_faces();
+        frame_copy.frame.locked_faces.swap(axis, axis + 3);
+        if let Some(coord) = &mut frame_copy.frame.slices[axis] {
+            *coord = faces[axis] + faces[axis + 3] - *coord;
+        }
+    }
+    frame_copy.offset(offset);
+    source.world_scope(|world| frame_copy.insert(world, copy));
+    source
+}
+
+/// The components that make up a copy of a frame.
+pub(crate) struct FrameCopy {
+    pub frame: BoxFrame,
+    pub transform: Transform,
+    pub parent: Option<ChildOf>,
+    pub precise: Option<PreciseFaces>,
+}
+
+impl FrameCopy {
+    /// A copy of the frame on `source`, if it is one.
+    pub fn of(source: &EntityWorldMut) -> Option<Self> {
+        Some(Self {
+            frame: source.get::<BoxFrame>()?.duplicate(),
+            transform: source.get::<Transform>().copied().unwrap_or_default(),
+            parent: source.get::<ChildOf>().cloned(),
+            precise: source.get::<PreciseFaces>().copied(),
+        })
+    }
+
+    /// Moves the copy by `offset` along its local axes.
+    pub fn offset(&mut self, offset: Vec3) {
+        match &mut self.precise {
+            // Precise frames derive their translation from their faces.
+            Some(precise) => {
+                let offset = offset.as_dvec3().to_array();
+                let mut faces = precise.faces();
+                for (face, coord) in faces.iter_mut().enumerate() {
+                    *coord += offset[face % 3];
+                }
+                precise.set_faces(faces);
+            }
+            None => {
+                self.transform.translation +=
+                    self.transform.rotation * (self.transform.scale * offset);
+            }
+        }
+    }
+
+    /// Moves the faces of the copy to `faces`, in local coordinates.
+    ///
+    /// [`PreciseFaces`] move by the same distances, so faces that don't move
+    /// keep their exact coordinates.
+    pub fn set_faces(&mut self, faces: [f32; 6]) {
+        let old_faces = self.frame.sorted_faces();
+        self.frame.set_faces(faces);
+        if let Some(precise) = &mut self.precise {
+            let mut precise_faces = precise.faces();
+            for (face, coord) in precise_faces.iter_mut().enumerate() {
+                if faces[face] != old_faces[face] {
+                    *coord += f64::from(faces[face]) - f64::from(old_faces[face]);
+                }
+            }
+            precise.set_faces(precise_faces);
+        }
+    }
+
+    /// Inserts the copy on `entity`, replacing any frame it had.
+    pub fn insert(self, world: &mut World, entity: Entity) {
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+        entity.insert((self.frame, self.transform));
+        if let Some(parent) = self.parent {
+            entity.insert(parent);
+        }
+        if let Some(precise) = self.precise {
+            entity.insert(precise);
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{
+    box_edges, sorted_faces, BoxFrame, BoxFrameOrigin, InteractionMode, PreciseFaces,
+    SolidColorMaterial,
+};
+use bevy::{
+    math::DVec3,
+    prelude::*,
+    render::{mesh::PrimitiveTopology, render_asset::RenderAssetUsages, view::NoFrustumCulling},
+};
+
+/// Draws grid lines on the faces of a [`BoxFrame`] and ruler ticks along its
+/// edges.
+///
+/// Spacings are along the frame's local axes, in local units. Lines are
+/// regenerated whenever the frame's faces or transform change, and thin out
+/// with distance: a spacing is doubled until it is at least
+/// [`min_pixel_spacing`](Self::min_pixel_spacing) apart on screen.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fsl_box_frame::{BoxFrame, BoxFrameGrid, GridAlignment};
+/// fn spawn_bench(mut commands: Commands) {
+///     commands.spawn((
+///         BoxFrame::new([0.0, 0.0, 0.0, 40.0, 10.0, 40.0]),
+///         BoxFrameGrid::new(5.0)
+///             .with_alignment(GridAlignment::World)
+///             .with_ticks(1.0, 0.25),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct BoxFrameGrid {
+    /// The distance between grid lines, or `0.0` for no grid lines.
+    pub spacing: f32,
+    /// Where grid lines and ticks are anchored.
+    pub alignment: GridAlignment,
+    /// The distance between ruler ticks, or `0.0` for no ticks.
+    pub tick_spacing: f32,
+    /// The length of each ruler tick, on both faces next to the edge.
+    pub tick_length: f32,
+    /// The minimum distance between lines or ticks on screen, in logical
+    /// pixels.
+    pub min_pixel_spacing: f32,
+    /// The color of grid lines and ticks.
+    pub color: Color,
+}
+
+/// Where the lines of a [`BoxFrameGrid`] are anchored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GridAlignment {
+    /// Lines are multiples of the spacing away from each minimum face.
+    #[default]
+    Box,
+    /// Lines are at multiples of the spacing from the world origin, including
+    /// the [`BoxFrameOrigin`], measured along the frame's local axes. Lines
+    /// stay put when faces are dragged.
+    World,
+}
+
+impl BoxFrameGrid {
+    /// Box-aligned grid lines with the given spacing and no ticks.
+    pub fn new(spacing: f32) -> Self {
+        Self {
+            spacing,
+            alignment: GridAlignment::default(),
+            tick_spacing: 0.0,
+            tick_length: 0.0,
+            min_pixel_spacing: 8.0,
+            color: Color::srgba(1.0, 1.0, 1.0, 0.3),
+        }
+    }
+
+    /// Sets where lines are anchored.
+    pub fn with_alignment(mut self, alignment: GridAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Adds ruler ticks with the given spacing and length.
+    pub fn with_ticks(mut self, spacing: f32, length: f32) -> Self {
+        self.tick_spacing = spacing;
+        self.tick_length = length;
+        self
+    }
+
+    /// Sets the color of lines and ticks.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// The entity that renders the lines of a [`BoxFrameGrid`].
+#[derive(Component)]
+pub(crate) struct GridLines {
+    entity: Entity,
+    mesh: Handle<Mesh>,
+    material: Handle<SolidColorMaterial>,
+    /// The grid and tick spacings after LOD, as last drawn.
+    spacings: [f32; 2],
+}
+
+/// More lines than this along an axis are thinned out regardless of distance.
+const MAX_LINES_PER_AXIS: f32 = 256.0;
+
+/// Rebuilds the line meshes of frames with a [`BoxFrameGrid`].
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn update_grid_lines(
+    mut commands: Commands,
+    origin: Res<BoxFrameOrigin>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut removed_grids: RemovedComponents<BoxFrameGrid>,
+    mut removed_frames: RemovedComponents<BoxFrame>,
+    orphaned: Query<&GridLines, Or<(Without<BoxFrameGrid>, Without<BoxFrame>)>>,
+    mut box_frames: Query<(
+        Entity,
+        Ref<BoxFrame>,
+        Ref<BoxFrameGrid>,
+        Ref<GlobalTransform>,
+        Option<&PreciseFaces>,
+        Option<&mut GridLines>,
+    )>,
+    mut visibility: Query<&mut Visibility>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<SolidColorMaterial>>,
+) {
+    for entity in removed_grids.read().chain(removed_frames.read()) {
+        if let Ok(lines) = orphaned.get(entity) {
+            if let Ok(mut lines_entity) = commands.get_entity(lines.entity) {
+                lines_entity.try_despawn();
+            }
+            commands.entity(entity).remove::<GridLines>();
+        }
+    }
+
+    for (entity, frame, grid, transform, precise, lines) in &mut box_frames {
+        let faces = sorted_faces(frame.faces());
+        let extents = Vec3::from(frame.extents());
+        let world_center = transform.transform_point(frame.center());
+        let world_scale = transform.scale().max_element();
+        let spacings = [grid.spacing, grid.tick_spacing].map(|spacing| {
+            lod_spacing(
+                spacing,
+                extents.max_element(),
+                grid.min_pixel_spacing,
+                world_center,
+                world_scale,
+                &cameras,
+            )
+        });
+
+        let mut lines = match lines {
+            Some(lines) => {
+                if !frame.is_changed()
+                    && !grid.is_changed()
+                    && !transform.is_changed()
+                    && !origin.is_changed()
+                    && lines.spacings == spacings
+                {
+                    continue;
+                }
+                lines
+            }
+            None => {
+                let mesh = meshes.add(Mesh::new(
+                    PrimitiveTopology::LineList,
+                    RenderAssetUsages::default(),
+                ));
+                let material = materials.add(grid_material(grid.color));
+                let lines_entity = commands
+                    .spawn((
+                        Mesh3d(mesh.clone()),
+                        MeshMaterial3d(material.clone()),
+                        // The mesh is modified in place, so its bounds go stale.
+                        NoFrustumCulling,
+                        // Hidden until its transform is propagated.
+                        Visibility::Hidden,
+                        ChildOf(entity),
+                    ))
+                    .id();
+                commands.entity(entity).insert(GridLines {
+                    entity: lines_entity,
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    spacings: [f32::NAN; 2],
+                });
+                // Drawn on the next update.
+                continue;
+            }
+        };
+        lines.spacings = spacings;
+
+        if grid.is_changed() {
+            if let Some(material) = materials.get_mut(&lines.material) {
+                *material = grid_material(grid.color);
+            }
+        }
+
+        // The coordinates of the frame's local origin along its local axes,
+        // relative to the world origin.
+        let anchor = match grid.alignment {
+            GridAlignment::Box => None,
+            GridAlignment::World => Some(match precise {
+                Some(precise) => {
+                    let precise = precise.faces();
+                    DVec3::from_array(std::array::from_fn(|axis| {
+                        0.5 * (precise[axis] + precise[axis + 3])
+                            - 0.5 * f64::from(faces[axis] + faces[axis + 3])
+                    }))
+                }
+                None => {
+                    let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+                    rotation.as_dquat().inverse() * (translation.as_dvec3() + origin.0)
+                        / scale.as_dvec3()
+                }
+            }),
+        };
+
+        let mut positions = Vec::new();
+        let [grid_spacing, tick_spacing] = spacings;
+        if grid_spacing > 0.0 {
+            grid_line_positions(faces, grid_spacing, anchor, &mut positions);
+        }
+        if tick_spacing > 0.0 && grid.tick_length > 0.0 {
+            tick_positions(
+                faces,
+                tick_spacing,
+                grid.tick_length,
+                anchor,
+                &mut positions,
+            );
+        }
+
+        if let Ok(mut vis) = visibility.get_mut(lines.entity) {
+            vis.set_if_neq(
+                if positions.is_empty() || frame.interaction == InteractionMode::Hidden {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                },
+            );
+        }
+        if let Some(mesh) = meshes.get_mut(&lines.mesh) {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        }
+    }
+}
+
+fn grid_material(color: Color) -> SolidColorMaterial {
+    SolidColorMaterial {
+        color: color.to_linear(),
+        alpha_mode: if color.alpha() < 1.0 {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        },
+    }
+}
+
+/// Doubles `spacing` until lines are at least `min_pixels` apart in the view of
+/// the closest camera, and no more than [`MAX_LINES_PER_AXIS`] fit in `extent`.
+fn lod_spacing(
+    spacing: f32,
+    extent: f32,
+    min_pixels: f32,
+    world_center: Vec3,
+    world_scale: f32,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+) -> f32 {
+    if spacing <= 0.0 {
+        return 0.0;
+    }
+    let pixels = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .filter_map(|(camera, camera_transform)| {
+            let a = camera
+                .world_to_viewport(camera_transform, world_center)
+                .ok()?;
+            let b = camera
+                .world_to_viewport(
+                    camera_transform,
+                    world_center + camera_transform.right() * spacing * world_scale,
+                )
+                .ok()?;
+            Some(a.distance(b))
+        })
+        .fold(0.0, f32::max);
+
+    let mut spacing = spacing;
+    if pixels > 0.0 {
+        let mut pixels = pixels;
+        while pixels < min_pixels {
+            spacing *= 2.0;
+            pixels *= 2.0;
+        }
+    }
+    while extent / spacing > MAX_LINES_PER_AXIS {
+        spacing *= 2.0;
+    }
+    spacing
+}
+
+/// Coordinates strictly between `min` and `max` at multiples of `spacing`,
+/// from `min`, or from `-anchor` if given.
+fn line_coords(min: f32, max: f32, spacing: f32, anchor: Option<f64>) -> Vec<f32> {
+    // Lines this close to a face would overlap its edges.
+    let epsilon = 1e-3 * spacing;
+    let spacing = f64::from(spacing);
+    let anchor = anchor.unwrap_or(-f64::from(min));
+    let first = ((f64::from(min) + anchor) / spacing).floor() as i64;
+    (first..)
+        .map(|i| (i as f64 * spacing - anchor) as f32)
+        .skip_while(|&coord| coord <= min + epsilon)
+        .take_while(|&coord| coord < max - epsilon)
+        .collect()
+}
+
+/// Appends the end points of grid lines on every face to `positions`.
+fn grid_line_positions(
+    faces: [f32; 6],
+    spacing: f32,
+    anchor: Option<DVec3>,
+    positions: &mut Vec<[f32; 3]>,
+) {
+    for face in 0..6 {
+        let axis = face % 3;
+        for (u, v) in [
+            ((axis + 1) % 3, (axis + 2) % 3),
+            ((axis + 2) % 3, (axis + 1) % 3),
+        ] {
+            // Lines at constant `u`, across `v`.
+            let anchor_u = anchor.map(|anchor| anchor[u]);
+            for coord in line_coords(faces[u], faces[u + 3], spacing, anchor_u) {
+                for end in [faces[v], faces[v + 3]] {
+                    let mut point = Vec3::ZERO;
+                    point[axis] = faces[face];
+                    point[u] = coord;
+                    point[v] = end;
+                    positions.push(point.to_array());
+                }
+            }
+        }
+    }
+}
+
+/// Appends the end points of ruler ticks along every edge to `positions`.
+fn tick_positions(
+    faces: [f32; 6],
+    spacing: f32,
+    length: f32,
+    anchor: Option<DVec3>,
+    positions: &mut Vec<[f32; 3]>,
+) {
+    let center = Vec3::from_array(std::array::from_fn(|axis| {
+        0.5 * (faces[axis] + faces[axis + 3])
+    }));
+    for ([a, b], edge_faces) in box_edges(faces) {
+        let along = (0..3).find(|&axis| a[axis] != b[axis]).unwrap_or(0);
+        let anchor = anchor.map(|anchor| anchor[along]);
+        for coord in line_coords(faces[along], faces[along + 3], spacing, anchor) {
+            let mut start = a;
+            start[along] = coord;
+            // A tick on each face next to the edge, pointing into the face.
+            for face in edge_faces {
+                let across = 3 - along - face % 3;
+                let mut end = start;
+                end[across] += length * (center[across] - start[across]).signum();
+                positions.extend([start.to_array(), end.to_array()]);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+/// Frames with the same group `id` are edited together: dragging a face of
+/// one frame also drags the equivalent face of every other frame in the group.
+///
+/// Equivalent faces have the same [`FaceIndex`](crate::FaceIndex), so grouped
+/// frames should share an orientation.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoxFrameGroup {
+    /// Identifies the group.
+    pub id: u32,
+    /// How the other frames follow a face dragged on this frame.
+    pub mode: GroupDragMode,
+}
+
+impl BoxFrameGroup {
+    /// A group whose faces move by the same distance.
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            mode: GroupDragMode::default(),
+        }
+    }
+}
+
+/// How grouped frames follow a face that is dragged on another frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GroupDragMode {
+    /// Equivalent faces move by the same distance as the dragged face.
+    #[default]
+    Delta,
+    /// Equivalent faces move to the same world-space plane as the dragged
+    /// face.
+    Coordinate,
+}
@@ -0,0 +1,119 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+        render_asset::RenderAssetUsages,
+    },
+};
+use parry3d::{
+    query::{Ray, RayCast},
+    shape::{self, SharedShape},
+};
+use std::fmt;
+
+/// The shape of the handle on each face of a [`BoxFrame`](crate::BoxFrame),
+/// used both to render and to pick the handle.
+///
+/// Shapes have a "radius" of 1 before the handle is scaled by
+/// [`BoxFrameVisuals::handle_scale`](crate::BoxFrameVisuals::handle_scale).
+/// Each handle is rotated so that its local `+Y` axis points along the normal
+/// of its face.
+#[derive(Clone, Default)]
+pub enum HandleShape {
+    /// A sphere.
+    #[default]
+    Ball,
+    /// A cube with a half-size of 1.
+    Cube,
+    /// A cone with a base radius of 1 and a height of 2 that points away from
+    /// the face.
+    Cone,
+    /// The convex hull of a custom mesh, created by
+    /// [`HandleShape::convex_hull`].
+    ConvexHull(SharedShape),
+}
+
+impl fmt::Debug for HandleShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ball => write!(f, "Ball"),
+            Self::Cube => write!(f, "Cube"),
+            Self::Cone => write!(f, "Cone"),
+            Self::ConvexHull(_) => write!(f, "ConvexHull(..)"),
+        }
+    }
+}
+
+impl HandleShape {
+    /// The convex hull of the vertex positions of `mesh`, or `None` if the
+    /// mesh has no `Float32x3` positions or they are degenerate.
+    ///
+    /// Pair this with `mesh` as the [`handle_mesh`] so handles are picked
+    /// where they are rendered.
+    ///
+    /// [`handle_mesh`]: crate::BoxFrameVisuals::handle_mesh
+    pub fn convex_hull(mesh: &Mesh) -> Option<Self> {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return None;
+        };
+        let points: Vec<_> = positions.iter().map(|&p| p.into()).collect();
+        SharedShape::convex_hull(&points).map(Self::ConvexHull)
+    }
+
+    /// A mesh that renders this shape.
+    pub fn mesh(&self) -> Mesh {
+        match self {
+            Self::Ball => Sphere::new(1.0).mesh().build(),
+            Self::Cube => Cuboid::from_length(2.0).mesh().build(),
+            Self::Cone => Cone::new(1.0, 2.0).mesh().build(),
+            Self::ConvexHull(shape) => {
+                let (vertices, indices) = shape
+                    .as_convex_polyhedron()
+                    .map(|hull| hull.to_trimesh())
+                    .unwrap_or_default();
+                let mut mesh = Mesh::new(
+                    PrimitiveTopology::TriangleList,
+                    RenderAssetUsages::default(),
+                )
+                .with_inserted_attribute(
+                    Mesh::ATTRIBUTE_POSITION,
+                    vertices
+                        .into_iter()
+                        .map(|p| [p.x, p.y, p.z])
+                        .collect::<Vec<_>>(),
+                )
+                .with_inserted_indices(Indices::U32(indices.into_iter().flatten().collect()));
+                mesh.duplicate_vertices();
+                mesh.compute_flat_normals();
+                mesh
+            }
+        }
+    }
+
+    /// The radius of a sphere around the origin that contains this shape.
+    pub(crate) fn bounding_radius(&self) -> f32 {
+        match self {
+            Self::Ball => 1.0,
+            Self::Cube => 3f32.sqrt(),
+            Self::Cone => 2f32.sqrt(),
+            Self::ConvexHull(shape) => {
+                let sphere = shape.compute_local_bounding_sphere();
+                sphere.center.coords.norm() + sphere.radius
+            }
+        }
+    }
+
+    /// Casts `ray`, given in the unscaled local space of the handle, against
+    /// this shape.
+    pub(crate) fn cast_local_ray(&self, ray: &Ray) -> Option<f32> {
+        let max_toi = f32::INFINITY;
+        match self {
+            Self::Ball => shape::Ball::new(1.0).cast_local_ray(ray, max_toi, true),
+            Self::Cube => shape::Cuboid::new([1.0; 3].into()).cast_local_ray(ray, max_toi, true),
+            Self::Cone => shape::Cone::new(1.0, 1.0).cast_local_ray(ray, max_toi, true),
+            Self::ConvexHull(shape) => shape.cast_local_ray(ray, max_toi, true),
+        }
+    }
+}
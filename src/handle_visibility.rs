@@ -1,45 +1,245 @@
-use crate::BoxFrame;
+use crate::{
+    box_frame::BoxFrameHandle, selection::selection_allows_editing, BoxFrame, BoxFrameSelection,
+    InteractionMode, Selected, SolidColorMaterial, FACE_NORMALS,
+};
 use bevy::{
     ecs::prelude::*,
-    picking::events::{Move, Out, Over, Pointer},
-    prelude::Visibility,
+    picking::hover::HoverMap,
+    platform::collections::HashSet,
+    prelude::{
+        Alpha, AlphaMode, Assets, Camera, DetectChangesMut, GlobalTransform, MeshMaterial3d,
+        Projection, Res, Time, Vec3, Visibility,
+    },
 };
+use bevy_polyline::prelude::PolylineMaterialHandle;
+
+/// Determines when the handles of a [`BoxFrame`] are shown.
+///
+/// Handles of faces that can't be dragged are never shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandleVisibility {
+    /// When handles are shown while no face is being dragged.
+    pub show: ShowHandles,
+    /// Only show the handles of faces that face a camera.
+    pub front_faces_only: bool,
+    /// Show the handle of the dragged face during a drag. The other handles
+    /// are always hidden while dragging.
+    pub keep_dragged_handle: bool,
+    /// How long handles take to fade in and out, in seconds. With `0.0`,
+    /// handles are shown and hidden instantly.
+    pub fade_seconds: f32,
+}
+
+impl Default for HandleVisibility {
+    fn default() -> Self {
+        Self {
+            show: ShowHandles::default(),
+            front_faces_only: false,
+            keep_dragged_handle: false,
+            fade_seconds: 0.0,
+        }
+    }
+}
+
+/// When [`HandleVisibility`] shows handles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ShowHandles {
+    /// Handles are always shown.
+    Always,
+    /// Handles are shown while a pointer hovers over the frame.
+    #[default]
+    OnHover,
+    /// Handles are shown while the frame is [`Selected`].
+    WhenSelected,
+}
 
+/// Where a camera looks from, for determining which faces it sees.
+enum CameraView {
+    /// A perspective camera at a position.
+    Position(Vec3),
+    /// An orthographic camera looking against a direction.
+    Direction(Vec3),
+}
+
+impl CameraView {
+    /// A vector from `point` towards the camera.
+    fn to_camera(&self, point: Vec3) -> Vec3 {
+        match *self {
+            Self::Position(position) => position - point,
+            Self::Direction(direction) => direction,
+        }
+    }
+}
+
+/// Shows and hides handles according to each frame's [`HandleVisibility`].
+///
+/// Only frames whose hover, selection, drag or fade state may have changed are
+/// updated.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn handle_visibility(
-    mut over_events: EventReader<Pointer<Over>>,
-    mut move_events: EventReader<Pointer<Move>>,
-    mut out_events: EventReader<Pointer<Out>>,
-    box_frames: Query<&BoxFrame>,
-    mut visibility: Query<&mut Visibility>,
+    time: Res<Time>,
+    hover_map: Res<HoverMap>,
+    selection: Option<Res<BoxFrameSelection>>,
+    mut had_selection: Local<bool>,
+    mut last_hovered: Local<HashSet<Entity>>,
+    mut pending: Local<HashSet<Entity>>,
+    changed_frames: Query<
+        Entity,
+        (
+            With<BoxFrame>,
+            Or<(Changed<BoxFrame>, Changed<GlobalTransform>, Added<Selected>)>,
+        ),
+    >,
+    mut deselected: RemovedComponents<Selected>,
+    box_frames: Query<(Entity, &BoxFrame, &GlobalTransform, Has<Selected>)>,
+    cameras: Query<(Ref<Camera>, Ref<GlobalTransform>, Option<Ref<Projection>>)>,
+    mut handles: Query<(
+        &mut BoxFrameHandle,
+        &mut Visibility,
+        &mut MeshMaterial3d<SolidColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<SolidColorMaterial>>,
 ) {
-    let normalized_over = over_events.read().map(|e| (e.target, Visibility::Visible));
-    let normalized_move = move_events.read().map(|e| (e.target, Visibility::Visible));
-    let normalized_out = out_events.read().map(|e| (e.target, Visibility::Hidden));
+    let hovered: HashSet<Entity> = hover_map
+        .values()
+        .flat_map(|hits| hits.keys().copied())
+        .collect();
+
+    // Frames with fades in progress, or whose handles didn't exist yet, are
+    // still pending from the last update.
+    let mut dirty = std::mem::take(&mut *pending);
+    dirty.extend(hovered.symmetric_difference(&last_hovered).copied());
+    dirty.extend(&changed_frames);
+    dirty.extend(deselected.read());
+    let selection_changed = selection
+        .as_ref()
+        .map_or(*had_selection, |selection| selection.is_changed());
+    *had_selection = selection.is_some();
+    let cameras_changed = cameras.iter().any(|(camera, transform, projection)| {
+        camera.is_changed() || transform.is_changed() || projection.is_some_and(|p| p.is_changed())
+    });
+    if selection_changed || cameras_changed {
+        dirty.extend(
+            box_frames
+                .iter()
+                .filter(|(_, frame, ..)| {
+                    selection_changed || frame.handle_visibility.front_faces_only
+                })
+                .map(|(entity, ..)| entity),
+        );
+    }
+    *last_hovered = hovered;
+    if dirty.is_empty() {
+        return;
+    }
+
+    let camera_views: Vec<_> = cameras
+        .iter()
+        .filter(|(camera, ..)| camera.is_active)
+        .map(|(_, transform, projection)| match projection.as_deref() {
+            Some(Projection::Orthographic(_)) => CameraView::Direction(*transform.back()),
+            _ => CameraView::Position(transform.translation()),
+        })
+        .collect();
 
-    for (target, set_visibility) in normalized_over.chain(normalized_move).chain(normalized_out) {
-        let Ok(frame) = box_frames.get(target) else {
-            continue;
+    for (entity, frame, transform, selected) in box_frames.iter_many(&dirty) {
+        let policy = frame.handle_visibility;
+        let editable = selection_allows_editing(selection.as_deref(), selected);
+        let shown = match policy.show {
+            ShowHandles::Always => true,
+            ShowHandles::OnHover => last_hovered.contains(&entity),
+            ShowHandles::WhenSelected => selected,
         };
+        let world_centers = frame
+            .face_centers()
+            .map(|center| transform.transform_point(center));
+        let rotation = transform.rotation();
 
-        for handle_enitity in frame.handle_entities() {
-            let Ok(mut vis) = visibility.get_mut(handle_enitity) else {
+        for (face, handle_entity) in frame.handle_entities().into_iter().enumerate() {
+            let Ok((mut handle, mut visibility, mut material)) = handles.get_mut(handle_entity)
+            else {
+                pending.insert(entity);
                 continue;
             };
-            *vis = set_visibility;
+
+            let visible = editable
+                && frame.can_drag_face(face)
+                && match frame.dragging_face {
+                    Some(dragging) => policy.keep_dragged_handle && dragging.face() == face,
+                    None => {
+                        let normal = rotation * FACE_NORMALS[face];
+                        shown
+                            && (!policy.front_faces_only
+                                || camera_views.iter().any(|view| {
+                                    view.to_camera(world_centers[face]).dot(normal) > 0.0
+                                }))
+                    }
+                };
+
+            let target = if visible { 1.0 } else { 0.0 };
+            let max_step = if policy.fade_seconds > 0.0 {
+                time.delta_secs() / policy.fade_seconds
+            } else {
+                1.0
+            };
+            let opacity = handle.opacity + (target - handle.opacity).clamp(-max_step, max_step);
+            if opacity != handle.opacity {
+                handle.opacity = opacity;
+            }
+            if opacity != target {
+                pending.insert(entity);
+            }
+            visibility.set_if_neq(if opacity > 0.0 {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            });
+
+            // Fading handles use their own translucent copy of the material.
+            let base_material = frame.visuals.handle_material(face);
+            if opacity <= 0.0 || opacity >= 1.0 {
+                if material.0 != *base_material {
+                    material.0 = base_material.clone();
+                }
+                continue;
+            }
+            let Some(base) = materials.get(base_material) else {
+                continue;
+            };
+            let faded = SolidColorMaterial {
+                color: base.color.with_alpha(base.color.alpha * opacity),
+                alpha_mode: AlphaMode::Blend,
+            };
+            if material.0 == *base_material {
+                material.0 = materials.add(faded);
+            } else if let Some(existing) = materials.get_mut(&material.0) {
+                *existing = faded;
+            }
         }
     }
+}
 
-    // Dragging frames never have visible handles.
-    for frame in &box_frames {
-        if frame.dragging_face.is_none() {
-            continue;
+/// Applies changes to [`BoxFrame::interaction`] that can't wait for the next
+/// pointer event.
+pub fn apply_interaction_mode(
+    box_frames: Query<(&BoxFrame, Has<Selected>), Changed<BoxFrame>>,
+    mut visibility: Query<&mut Visibility>,
+    mut line_materials: Query<&mut PolylineMaterialHandle>,
+) {
+    for (frame, selected) in &box_frames {
+        let face_visibility = if frame.interaction == InteractionMode::Hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        for face_entity in frame.face_entities() {
+            if let Ok(mut vis) = visibility.get_mut(face_entity) {
+                vis.set_if_neq(face_visibility);
+            }
         }
 
-        for handle_enitity in frame.handle_entities() {
-            let Ok(mut vis) = visibility.get_mut(handle_enitity) else {
-                continue;
-            };
-            *vis = Visibility::Hidden;
+        if !frame.interaction.is_pickable() {
+            frame.clear_highlights(selected, &mut line_materials);
         }
     }
 }
@@ -1,40 +1,89 @@
-use crate::{face_index_from_world_normal, BoxFrame, BoxFrameHandle};
-use bevy::prelude::*;
+use crate::{
+    face_index_from_world_normal, selection::selection_allows_editing, BoxFrame, BoxFrameHandle,
+    BoxFrameSelection, FaceIndex, Selected,
+};
+use bevy::{
+    picking::{hover::HoverMap, pointer::PointerId},
+    prelude::*,
+};
 use bevy_polyline::prelude::PolylineMaterialHandle;
 
+/// The face of a [`BoxFrame`] under a pointer, if any.
+///
+/// Updated by [`BoxFramePlugin`](crate::BoxFramePlugin) when pointers move,
+/// even if the face can't be dragged. While a face is dragged, this is the
+/// dragged face.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoxFrameHover(pub Option<HoveredFace>);
+
+impl BoxFrameHover {
+    /// The hovered face.
+    pub fn face(&self) -> Option<FaceIndex> {
+        self.0.map(|hovered| hovered.face)
+    }
+}
+
+/// A face of a [`BoxFrame`] under a pointer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HoveredFace {
+    /// The hovered face.
+    pub face: FaceIndex,
+    /// The handle of the face, if the pointer is over it.
+    pub handle: Option<Entity>,
+    /// Where the pointer ray hit the frame, in world coordinates.
+    pub position: Vec3,
+    /// The hovering pointer.
+    pub pointer: PointerId,
+    /// The camera that the pointer ray was cast from.
+    pub camera: Entity,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn highlight_face(
     mut over_events: EventReader<Pointer<Over>>,
     mut move_events: EventReader<Pointer<Move>>,
     mut out_events: EventReader<Pointer<Out>>,
     mut drag_end_events: EventReader<Pointer<DragEnd>>,
-    box_frames: Query<(&BoxFrame, &GlobalTransform)>,
+    selection: Option<Res<BoxFrameSelection>>,
+    hover_map: Res<HoverMap>,
+    mut box_frames: Query<(
+        &BoxFrame,
+        &GlobalTransform,
+        Has<Selected>,
+        &mut BoxFrameHover,
+    )>,
     mut line_handles: Query<&mut PolylineMaterialHandle>,
 ) {
     // Prioritize highlighting based on faces being dragged.
-    for (frame, _) in &box_frames {
+    for (frame, _, selected, mut hover) in &mut box_frames {
         if let Some(dragging) = &frame.dragging_face {
-            frame.clear_highlights(&mut line_handles);
+            frame.clear_highlights(selected, &mut line_handles);
             frame.highlight_face(dragging.face(), &mut line_handles);
+            if let Some(hovered) = &mut hover.0 {
+                hovered.face = dragging.face();
+            }
         }
     }
 
     let normalized_over = over_events
         .read()
-        .map(|e| (e.target, Some(e.event.hit.clone())));
+        .map(|e| (e.target, e.pointer_id, Some(e.event.hit.clone())));
     let normalized_move = move_events
         .read()
-        .map(|e| (e.target, Some(e.event.hit.clone())));
-    let normalized_out = out_events.read().map(|e| (e.target, None));
-    let normalized_drag_end = drag_end_events.read().map(|e| (e.target, None));
+        .map(|e| (e.target, e.pointer_id, Some(e.event.hit.clone())));
+    let normalized_out = out_events.read().map(|e| (e.target, e.pointer_id, None));
+    let normalized_drag_end = drag_end_events
+        .read()
+        .map(|e| (e.target, e.pointer_id, None));
 
     // Highlight faces intersecting a pointer ray. "Out" events will clear all
     // highlights.
-    for (target, maybe_pick_data) in normalized_over
+    for (target, pointer, maybe_pick_data) in normalized_over
         .chain(normalized_move)
         .chain(normalized_out)
         .chain(normalized_drag_end)
     {
-        let Ok((frame, transform)) = box_frames.get(target) else {
+        let Ok((frame, transform, selected, mut hover)) = box_frames.get_mut(target) else {
             continue;
         };
 
@@ -44,11 +93,31 @@ pub(crate) fn highlight_face(
             continue;
         }
 
-        frame.clear_highlights(&mut line_handles);
-        if let Some(pick_data) = maybe_pick_data {
-            if let Some(world_normal) = pick_data.normal {
-                let picked_face = face_index_from_world_normal(world_normal, transform);
-                frame.highlight_face(picked_face, &mut line_handles);
+        let hovered = maybe_pick_data.and_then(|pick_data| {
+            let face = face_index_from_world_normal(pick_data.normal?, transform);
+            let handle = frame.handle_entities()[face];
+            Some(HoveredFace {
+                face,
+                handle: hover_map
+                    .get(&pointer)
+                    .is_some_and(|hits| hits.contains_key(&handle))
+                    .then_some(handle),
+                position: pick_data.position?,
+                pointer,
+                camera: pick_data.camera,
+            })
+        });
+        if hovered.is_some() || hover.0.is_some_and(|h| h.pointer == pointer) {
+            hover.set_if_neq(BoxFrameHover(hovered));
+        }
+
+        frame.clear_highlights(selected, &mut line_handles);
+        if !selection_allows_editing(selection.as_deref(), selected) {
+            continue;
+        }
+        if let Some(hovered) = hovered {
+            if !frame.is_face_locked(hovered.face) {
+                frame.highlight_face(hovered.face, &mut line_handles);
             }
         }
     }
@@ -0,0 +1,629 @@
+//! Export and import of box frames in interchange formats.
+//!
+//! Each box is described by its 8 world-space corners, [`BoxFrameCorners`].
+//! Every format stores those corners, so every format can be imported again:
+//!
+//! | Format | Write | Read | Boxes are |
+//! |---|---|---|---|
+//! | Wavefront OBJ | [`write_obj`] | [`read_obj`] | objects (`o`) with 8 vertices and 6 quads |
+//! | STL | [`write_stl_ascii`], [`write_stl_binary`] | [`read_stl`] | ASCII solids, or runs of 12 binary triangles |
+//! | DXF | [`write_dxf`] | [`read_dxf`] | runs of 6 `3DFACE`s or closed `POLYLINE`s |
+//! | GeoJSON | [`write_geojson`] | [`read_geojson`] | `MultiPoint` features |
+//! | CSV | [`write_csv`] | [`read_csv`] | 8 rows with the same `frame` |
+//!
+//! Imported corners are fitted with an oriented box, so any box survives a
+//! round trip, although its local axes may be permuted.
+//!
+//! Requires the `interchange` feature.
+
+use crate::{corner_vertices, BoxFrame, BoxFrameOrigin, FACE_QUADS};
+use bevy::{
+    math::{DMat3, DVec3},
+    prelude::*,
+};
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Read, Write},
+};
+
+/// The world-space corners of a box, in high-precision coordinates.
+///
+/// Corners are indexed by `0bZYX`, where each bit selects the minimum (`0`) or
+/// maximum (`1`) face along that local axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxFrameCorners(pub [DVec3; 8]);
+
+impl BoxFrameCorners {
+    /// The corners of `frame` with its world transform applied.
+    pub fn new(frame: &BoxFrame, transform: &GlobalTransform, origin: &BoxFrameOrigin) -> Self {
+        Self(
+            corner_vertices(frame.faces())
+                .map(|corner| origin.from_world(transform.transform_point(corner))),
+        )
+    }
+
+    /// Fits an oriented box to 8 corners in any order, or returns `None` if
+    /// they aren't the corners of a box with a nonzero size.
+    ///
+    /// Of the rotations that fit the corners, the one closest to the identity
+    /// is chosen.
+    pub fn from_points(points: &[DVec3]) -> Option<Self> {
+        let [first, ref others @ ..] = *points else {
+            return None;
+        };
+        if others.len() != 7 {
+            return None;
+        }
+        let edges: Vec<_> = others.iter().map(|&p| p - first).collect();
+        let scale = edges.iter().map(|e| e.length()).fold(0.0, f64::max);
+        let tolerance = 1e-4 * scale;
+        let orthogonal = |a: DVec3, b: DVec3| a.dot(b).abs() <= 1e-4 * a.length() * b.length();
+
+        // Find the three edges at the first corner.
+        let mut box_edges = None;
+        'search: for i in 0..7 {
+            for j in i + 1..7 {
+                for k in j + 1..7 {
+                    let [a, b, c] = [edges[i], edges[j], edges[k]];
+                    if [a, b, c].iter().any(|e| e.length() <= tolerance)
+                        || !orthogonal(a, b)
+                        || !orthogonal(b, c)
+                        || !orthogonal(a, c)
+                    {
+                        continue;
+                    }
+                    let covered = (1..8).all(|bits: usize| {
+                        let corner = first
+                            + [a, b, c]
+                                .iter()
+                                .enumerate()
+                                .filter(|(axis, _)| bits & (1 << axis) != 0)
+                                .map(|(_, &e)| e)
+                                .sum::<DVec3>();
+                        others.iter().any(|&p| p.distance(corner) <= tolerance)
+                    });
+                    if covered {
+                        box_edges = Some([a, b, c]);
+                        break 'search;
+                    }
+                }
+            }
+        }
+        let box_edges = box_edges?;
+
+        // Pick the right-handed assignment of edges to local axes that is
+        // closest to the world axes.
+        let center = points.iter().sum::<DVec3>() / 8.0;
+        let mut best: Option<(f64, [DVec3; 3])> = None;
+        for [i, j, k] in [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ] {
+            for signs in 0..8 {
+                let sign = |axis: usize| if signs & (1 << axis) != 0 { -1.0 } else { 1.0 };
+                let axes = [
+                    sign(0) * box_edges[i],
+                    sign(1) * box_edges[j],
+                    sign(2) * box_edges[k],
+                ];
+                if axes[0].cross(axes[1]).dot(axes[2]) <= 0.0 {
+                    continue;
+                }
+                let score = axes[0].normalize().x + axes[1].normalize().y + axes[2].normalize().z;
+                if best.is_none_or(|(best_score, _)| score > best_score) {
+                    best = Some((score, axes));
+                }
+            }
+        }
+        let (_, axes) = best?;
+        Some(Self(std::array::from_fn(|bits| {
+            center
+                + axes
+                    .iter()
+                    .enumerate()
+                    .map(|(axis, &e)| {
+                        if bits & (1 << axis) != 0 {
+                            0.5 * e
+                        } else {
+                            -0.5 * e
+                        }
+                    })
+                    .sum::<DVec3>()
+        })))
+    }
+
+    /// A frame with these corners, centered on the translation of the
+    /// returned transform.
+    ///
+    /// The corners must be those of a box, e.g. from
+    /// [`from_points`](Self::from_points).
+    pub fn to_frame(&self, origin: &BoxFrameOrigin) -> (BoxFrame, Transform) {
+        let [c0, x, y, .., z, _, _, _] = self.0;
+        let edges = [x - c0, y - c0, z - c0];
+        let half = DVec3::new(edges[0].length(), edges[1].length(), edges[2].length()) / 2.0;
+        let rotation = DMat3::from_cols(
+            edges[0].normalize_or_zero(),
+            edges[1].normalize_or_zero(),
+            edges[2].normalize_or_zero(),
+        );
+        let center = self.0.iter().sum::<DVec3>() / 8.0;
+        let half = half.as_vec3();
+        (
+            BoxFrame::new([-half.x, -half.y, -half.z, half.x, half.y, half.z]),
+            Transform::from_translation(origin.to_world(center))
+                .with_rotation(Quat::from_mat3(&rotation.as_mat3()).normalize()),
+        )
+    }
+
+    /// The corner indices of each face, wound counterclockwise when viewed
+    /// from outside the box.
+    fn outward_quads(&self) -> [[usize; 4]; 6] {
+        let center = self.0.iter().sum::<DVec3>() / 8.0;
+        FACE_QUADS.map(|quad| {
+            let [a, b, c, _] = quad.map(|corner| self.0[corner]);
+            if (b - a).cross(c - a).dot((a + c) / 2.0 - center) < 0.0 {
+                [quad[0], quad[3], quad[2], quad[1]]
+            } else {
+                quad
+            }
+        })
+    }
+}
+
+/// The axes of the coordinates in a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Axes {
+    /// Bevy's axes: `+Y` is up and `-Z` is north.
+    #[default]
+    YUp,
+    /// `+Z` is up and `+Y` is north, as in most CAD, GIS and mine planning
+    /// tools.
+    ZUp,
+}
+
+impl Axes {
+    fn export(self, p: DVec3) -> DVec3 {
+        match self {
+            Self::YUp => p,
+            Self::ZUp => DVec3::new(p.x, -p.z, p.y),
+        }
+    }
+
+    fn import(self, p: DVec3) -> DVec3 {
+        match self {
+            Self::YUp => p,
+            Self::ZUp => DVec3::new(p.x, p.z, -p.y),
+        }
+    }
+}
+
+/// The entities that [`write_dxf`] uses for each face.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DxfEntity {
+    /// A `3DFACE`.
+    #[default]
+    Face,
+    /// A closed 3D `POLYLINE`.
+    Polyline,
+}
+
+/// Writes each box as an object with 8 vertices and 6 quads.
+pub fn write_obj(boxes: &[BoxFrameCorners], axes: Axes, mut w: impl Write) -> io::Result<()> {
+    for (index, corners) in boxes.iter().enumerate() {
+        writeln!(w, "o frame_{index}")?;
+        for corner in corners.0 {
+            let [x, y, z] = axes.export(corner).to_array();
+            writeln!(w, "v {x} {y} {z}")?;
+        }
+        let first_vertex = 8 * index + 1;
+        for quad in corners.outward_quads() {
+            let [a, b, c, d] = quad.map(|corner| first_vertex + corner);
+            writeln!(w, "f {a} {b} {c} {d}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the boxes written by [`write_obj`]: each object or group must have 8
+/// vertices. Without objects or groups, every 8 vertices form a box.
+pub fn read_obj(r: impl BufRead, axes: Axes) -> io::Result<Vec<BoxFrameCorners>> {
+    let mut groups: Vec<Vec<DVec3>> = Vec::new();
+    let mut new_group = true;
+    for line in r.lines() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("o" | "g") => new_group = true,
+            Some("v") => {
+                if new_group || groups.last().is_some_and(|g| g.len() == 8) {
+                    groups.push(Vec::new());
+                    new_group = false;
+                }
+                let point = parse_point(tokens.take(3))?;
+                groups.last_mut().unwrap().push(axes.import(point));
+            }
+            _ => {}
+        }
+    }
+    groups.iter().map(|points| fit(points)).collect()
+}
+
+/// Writes each box as an ASCII solid with 12 triangles.
+pub fn write_stl_ascii(boxes: &[BoxFrameCorners], axes: Axes, mut w: impl Write) -> io::Result<()> {
+    for (index, corners) in boxes.iter().enumerate() {
+        writeln!(w, "solid frame_{index}")?;
+        for triangle in triangles(corners) {
+            let [a, b, c] = triangle.map(|p| axes.export(p));
+            let [nx, ny, nz] = (b - a).cross(c - a).normalize_or_zero().to_array();
+            writeln!(w, "  facet normal {nx} {ny} {nz}")?;
+            writeln!(w, "    outer loop")?;
+            for [x, y, z] in [a, b, c].map(|p| p.to_array()) {
+                writeln!(w, "      vertex {x} {y} {z}")?;
+            }
+            writeln!(w, "    endloop")?;
+            writeln!(w, "  endfacet")?;
+        }
+        writeln!(w, "endsolid frame_{index}")?;
+    }
+    Ok(())
+}
+
+/// Writes all boxes to one binary solid, 12 triangles per box, in `f32`.
+pub fn write_stl_binary(
+    boxes: &[BoxFrameCorners],
+    axes: Axes,
+    mut w: impl Write,
+) -> io::Result<()> {
+    let mut header = [0; 80];
+    let name = b"bevy_fsl_box_frame";
+    header[..name.len()].copy_from_slice(name);
+    w.write_all(&header)?;
+    let count = u32::try_from(12 * boxes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many boxes"))?;
+    w.write_all(&count.to_le_bytes())?;
+    for corners in boxes {
+        for triangle in triangles(corners) {
+            let [a, b, c] = triangle.map(|p| axes.export(p));
+            let normal = (b - a).cross(c - a).normalize_or_zero();
+            for v in [normal, a, b, c] {
+                for coord in v.as_vec3().to_array() {
+                    w.write_all(&coord.to_le_bytes())?;
+                }
+            }
+            w.write_all(&[0; 2])?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the boxes written by [`write_stl_ascii`] or [`write_stl_binary`].
+///
+/// Each ASCII solid is a box. In binary files, every 12 triangles form a box.
+pub fn read_stl(mut r: impl Read, axes: Axes) -> io::Result<Vec<BoxFrameCorners>> {
+    let mut bytes = Vec::new();
+    r.read_to_end(&mut bytes)?;
+    let binary_len =
+        |count: &[u8]| 84 + 50 * u32::from_le_bytes(count.try_into().unwrap()) as usize;
+    if bytes.len() >= 84 && bytes.len() == binary_len(&bytes[80..84]) {
+        return read_stl_binary(&bytes[84..], axes);
+    }
+
+    let text = std::str::from_utf8(&bytes).map_err(invalid_data)?;
+    let mut groups: Vec<Vec<DVec3>> = Vec::new();
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("solid") => groups.push(Vec::new()),
+            Some("vertex") => {
+                let point = axes.import(parse_point(tokens.take(3))?);
+                let group = groups
+                    .last_mut()
+                    .ok_or_else(|| invalid_data("vertex outside of a solid"))?;
+                push_unique(group, point);
+            }
+            _ => {}
+        }
+    }
+    groups.iter().map(|points| fit(points)).collect()
+}
+
+fn read_stl_binary(triangles: &[u8], axes: Axes) -> io::Result<Vec<BoxFrameCorners>> {
+    let triangles: Vec<_> = triangles.chunks_exact(50).collect();
+    if !triangles.len().is_multiple_of(12) {
+        return Err(invalid_data("the triangle count isn't a multiple of 12"));
+    }
+    triangles
+        .chunks(12)
+        .map(|box_triangles| {
+            let mut points = Vec::new();
+            for triangle in box_triangles {
+                // Skip the normal.
+                for vertex in triangle[12..48].chunks_exact(12) {
+                    let coords: Vec<_> = vertex
+                        .chunks_exact(4)
+                        .map(|c| f64::from(f32::from_le_bytes(c.try_into().unwrap())))
+                        .collect();
+                    let point = DVec3::new(coords[0], coords[1], coords[2]);
+                    push_unique(&mut points, axes.import(point));
+                }
+            }
+            fit(&points)
+        })
+        .collect()
+}
+
+/// Writes each box as 6 consecutive entities, one per face.
+pub fn write_dxf(
+    boxes: &[BoxFrameCorners],
+    axes: Axes,
+    entity: DxfEntity,
+    mut w: impl Write,
+) -> io::Result<()> {
+    writeln!(w, "0\nSECTION\n2\nENTITIES")?;
+    for corners in boxes {
+        for quad in corners.outward_quads() {
+            let quad = quad.map(|corner| axes.export(corners.0[corner]));
+            match entity {
+                DxfEntity::Face => {
+                    writeln!(w, "0\n3DFACE\n8\nBOX_FRAMES")?;
+                    for (i, [x, y, z]) in quad.map(|p| p.to_array()).into_iter().enumerate() {
+                        writeln!(w, "1{i}\n{x}\n2{i}\n{y}\n3{i}\n{z}")?;
+                    }
+                }
+                DxfEntity::Polyline => {
+                    // A closed 3D polyline.
+                    writeln!(w, "0\nPOLYLINE\n8\nBOX_FRAMES\n66\n1\n70\n9")?;
+                    for [x, y, z] in quad.map(|p| p.to_array()) {
+                        writeln!(
+                            w,
+                            "0\nVERTEX\n8\nBOX_FRAMES\n10\n{x}\n20\n{y}\n30\n{z}\n70\n32"
+                        )?;
+                    }
+                    writeln!(w, "0\nSEQEND\n8\nBOX_FRAMES")?;
+                }
+            }
+        }
+    }
+    writeln!(w, "0\nENDSEC\n0\nEOF")
+}
+
+/// Reads the boxes written by [`write_dxf`]: every 6 `3DFACE` or `POLYLINE`
+/// entities form a box.
+pub fn read_dxf(r: impl BufRead, axes: Axes) -> io::Result<Vec<BoxFrameCorners>> {
+    let lines: Vec<String> = r.lines().collect::<io::Result<_>>()?;
+    let mut faces: Vec<Vec<DVec3>> = Vec::new();
+    let mut entity = String::new();
+    let mut in_polyline = false;
+    let mut point = [None; 3];
+    let mut face_points = [[None::<f64>; 3]; 4];
+
+    let finish_entity = |entity: &str,
+                         point: &mut [Option<f64>; 3],
+                         face_points: &mut [[Option<f64>; 3]; 4],
+                         faces: &mut Vec<Vec<DVec3>>|
+     -> io::Result<()> {
+        match entity {
+            "3DFACE" => {
+                let mut face = Vec::new();
+                for p in face_points.iter() {
+                    face.push(axes.import(complete_point(*p)?));
+                }
+                faces.push(face);
+            }
+            "VERTEX" => {
+                let p = axes.import(complete_point(*point)?);
+                faces
+                    .last_mut()
+                    .ok_or_else(|| invalid_data("VERTEX outside of a POLYLINE"))?
+                    .push(p);
+            }
+            _ => {}
+        }
+        *point = [None; 3];
+        *face_points = [[None; 3]; 4];
+        Ok(())
+    };
+
+    for pair in lines.chunks_exact(2) {
+        let code: u32 = pair[0].trim().parse().map_err(invalid_data)?;
+        let value = pair[1].trim();
+        match code {
+            0 => {
+                finish_entity(&entity, &mut point, &mut face_points, &mut faces)?;
+                entity = value.to_string();
+                match value {
+                    "POLYLINE" => {
+                        in_polyline = true;
+                        faces.push(Vec::new());
+                    }
+                    "SEQEND" => in_polyline = false,
+                    _ => {}
+                }
+            }
+            10..=13 | 20..=23 | 30..=33 if entity == "3DFACE" => {
+                let coord = value.parse().map_err(invalid_data)?;
+                face_points[(code % 10) as usize][(code / 10 - 1) as usize] = Some(coord);
+            }
+            10 | 20 | 30 if entity == "VERTEX" && in_polyline => {
+                point[(code / 10 - 1) as usize] = Some(value.parse().map_err(invalid_data)?);
+            }
+            _ => {}
+        }
+    }
+    finish_entity(&entity, &mut point, &mut face_points, &mut faces)?;
+
+    if !faces.len().is_multiple_of(6) {
+        return Err(invalid_data("the face count isn't a multiple of 6"));
+    }
+    faces
+        .chunks(6)
+        .map(|box_faces| {
+            let mut points = Vec::new();
+            for &p in box_faces.iter().flatten() {
+                push_unique(&mut points, p);
+            }
+            fit(&points)
+        })
+        .collect()
+}
+
+/// Writes a feature collection with a `MultiPoint` feature of 8 corners per
+/// box. GeoJSON coordinates are usually [`Axes::ZUp`].
+pub fn write_geojson(boxes: &[BoxFrameCorners], axes: Axes, mut w: impl Write) -> io::Result<()> {
+    writeln!(w, r#"{{"type":"FeatureCollection","features":["#)?;
+    for (index, corners) in boxes.iter().enumerate() {
+        let coordinates: Vec<_> = corners
+            .0
+            .map(|p| {
+                let [x, y, z] = axes.export(p).to_array();
+                format!("[{x},{y},{z}]")
+            })
+            .to_vec();
+        let separator = if index + 1 < boxes.len() { "," } else { "" };
+        writeln!(
+            w,
+            r#"{{"type":"Feature","properties":{{"frame":{index}}},"geometry":{{"type":"MultiPoint","coordinates":[{}]}}}}{separator}"#,
+            coordinates.join(",")
+        )?;
+    }
+    writeln!(w, "]}}")
+}
+
+/// Reads the boxes written by [`write_geojson`]: the coordinates of each
+/// geometry must be a list of 8 positions.
+pub fn read_geojson(mut r: impl Read, axes: Axes) -> io::Result<Vec<BoxFrameCorners>> {
+    let mut text = String::new();
+    r.read_to_string(&mut text)?;
+    let key = r#""coordinates""#;
+    let mut boxes = Vec::new();
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find(key) {
+        rest = rest[start + key.len()..].trim_start();
+        rest = rest
+            .strip_prefix(':')
+            .ok_or_else(|| invalid_data("expected ':' after \"coordinates\""))?;
+        let (positions, remaining) = parse_positions(rest)?;
+        rest = remaining;
+        let points: Vec<_> = positions.into_iter().map(|p| axes.import(p)).collect();
+        boxes.push(fit(&points)?);
+    }
+    Ok(boxes)
+}
+
+/// Writes a `frame,corner,x,y,z` row for each corner of each box.
+pub fn write_csv(boxes: &[BoxFrameCorners], axes: Axes, mut w: impl Write) -> io::Result<()> {
+    writeln!(w, "frame,corner,x,y,z")?;
+    for (index, corners) in boxes.iter().enumerate() {
+        for (corner, p) in corners.0.into_iter().enumerate() {
+            let [x, y, z] = axes.export(p).to_array();
+            writeln!(w, "{index},{corner},{x},{y},{z}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the boxes written by [`write_csv`]. Rows are grouped by their
+/// `frame` column, and the first line is skipped as a header.
+pub fn read_csv(r: impl BufRead, axes: Axes) -> io::Result<Vec<BoxFrameCorners>> {
+    let mut groups = BTreeMap::<String, Vec<DVec3>>::new();
+    for line in r.lines().skip(1) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<_> = line.split(',').map(str::trim).collect();
+        let [frame, _corner, x, y, z] = fields[..] else {
+            return Err(invalid_data(format!("expected 5 fields in {line:?}")));
+        };
+        let point = parse_point([x, y, z].into_iter())?;
+        groups
+            .entry(frame.to_string())
+            .or_default()
+            .push(axes.import(point));
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    // Numeric frame indices sort numerically.
+    groups.sort_by_key(|(frame, _)| (frame.parse::<u64>().ok(), frame.clone()));
+    groups.iter().map(|(_, points)| fit(points)).collect()
+}
+
+fn triangles(corners: &BoxFrameCorners) -> impl Iterator<Item = [DVec3; 3]> + '_ {
+    corners
+        .outward_quads()
+        .into_iter()
+        .map(|quad| quad.map(|corner| corners.0[corner]))
+        .flat_map(|[a, b, c, d]| [[a, b, c], [a, c, d]])
+}
+
+fn fit(points: &[DVec3]) -> io::Result<BoxFrameCorners> {
+    BoxFrameCorners::from_points(points).ok_or_else(|| {
+        invalid_data(format!(
+            "expected the 8 corners of a box, found {} points",
+            points.len()
+        ))
+    })
+}
+
+fn push_unique(points: &mut Vec<DVec3>, point: DVec3) {
+    if !points.contains(&point) {
+        points.push(point);
+    }
+}
+
+fn parse_point<'a>(mut tokens: impl Iterator<Item = &'a str>) -> io::Result<DVec3> {
+    let mut coord = || -> io::Result<f64> {
+        tokens
+            .next()
+            .ok_or_else(|| invalid_data("expected 3 coordinates"))?
+            .parse()
+            .map_err(invalid_data)
+    };
+    Ok(DVec3::new(coord()?, coord()?, coord()?))
+}
+
+fn complete_point(point: [Option<f64>; 3]) -> io::Result<DVec3> {
+    match point {
+        [Some(x), Some(y), Some(z)] => Ok(DVec3::new(x, y, z)),
+        _ => Err(invalid_data("incomplete DXF point")),
+    }
+}
+
+/// Parses a JSON array of `[x, y, z]` positions, returning the rest of the
+/// input.
+fn parse_positions(input: &str) -> io::Result<(Vec<DVec3>, &str)> {
+    let mut rest = expect(input, '[')?;
+    let mut positions = Vec::new();
+    loop {
+        if let Ok(after) = expect(rest, ']') {
+            return Ok((positions, after));
+        }
+        if !positions.is_empty() {
+            rest = expect(rest, ',')?;
+        }
+        rest = expect(rest, '[')?;
+        let end = rest
+            .find(']')
+            .ok_or_else(|| invalid_data("unterminated GeoJSON position"))?;
+        let coords: Vec<&str> = rest[..end].split(',').map(str::trim).collect();
+        if coords.len() < 3 {
+            return Err(invalid_data("GeoJSON positions need 3 coordinates"));
+        }
+        positions.push(parse_point(coords.into_iter())?);
+        rest = &rest[end + 1..];
+    }
+}
+
+fn expect(input: &str, c: char) -> io::Result<&str> {
+    input
+        .trim_start()
+        .strip_prefix(c)
+        .ok_or_else(|| invalid_data(format!("expected '{c}' in GeoJSON coordinates")))
+}
+
+fn invalid_data(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
@@ -1,31 +1,85 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+mod animation;
+mod batch;
 mod box_frame;
+mod broad_phase;
+mod collision;
+#[cfg(feature = "cursor")]
+mod cursor;
 mod drag_face;
+mod duplicate;
+mod grid;
+mod group;
+mod handle_shape;
 mod handle_visibility;
 mod highlight;
+#[cfg(feature = "interchange")]
+pub mod interchange;
 mod picking_backend;
+mod precision;
+mod selection;
+mod slice;
+mod snapping;
 mod solid_color_material;
+mod split;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod theme;
 
+pub use animation::BoxFrameAnimation;
+pub use batch::BoxFrameBatch;
 pub use box_frame::*;
+pub use collision::{BoxFrameBlocked, BoxFrameCollisionGroup};
+#[cfg(feature = "cursor")]
+pub use cursor::BoxFrameCursorPlugin;
+pub use duplicate::{BoxFrameArray, BoxFrameCommandsExt};
+pub use grid::{BoxFrameGrid, GridAlignment};
+pub use group::*;
+pub use handle_shape::HandleShape;
+pub use handle_visibility::{HandleVisibility, ShowHandles};
+pub use highlight::{BoxFrameHover, HoveredFace};
+pub use precision::{BoxFrameOrigin, PreciseFaces};
+pub use selection::{BoxFrameSelection, BoxFrameSelectionPlugin, Selected};
+pub use snapping::{BoxFrameSnapping, SnapTarget};
 pub use solid_color_material::*;
+pub use split::{BoxFrameDivided, BoxFrameSplit, BoxFramesMerged};
+pub use theme::{BoxFrameStyle, BoxFrameTheme};
 
+use animation::animate_faces;
+use batch::{promote_batched_frames, update_box_frame_batch};
 use bevy::{
     asset::load_internal_asset,
     picking::PickSet,
-    prelude::{IntoScheduleConfigs, MaterialPlugin, Plugin, PreUpdate, Shader, Update},
+    prelude::{
+        resource_exists_and_changed, App, Assets, IntoScheduleConfigs, MaterialPlugin, Mesh, Mut,
+        Plugin, PostUpdate, PreUpdate, Shader, TransformSystem, Update,
+    },
+    render::view::VisibilitySystems,
 };
+use bevy_polyline::prelude::PolylineMaterial;
+use broad_phase::{update_broad_phase, BoxFrameBroadPhase};
 use drag_face::*;
+use grid::update_grid_lines;
 use handle_visibility::*;
 use highlight::*;
 use picking_backend::box_frame_backend;
+use precision::sync_precise_faces;
+use selection::update_selection_visuals;
+use slice::{drag_slice, update_slice_planes};
+use snapping::{draw_snap_indicators, ActiveSnaps};
+use theme::apply_theme;
 
 /// Enables pointer interactions for [`BoxFrame`] entities.
-pub struct BoxFramePlugin;
+#[derive(Default)]
+pub struct BoxFramePlugin {
+    /// The style of the [`BoxFrameTheme`] created by this plugin.
+    pub style: BoxFrameStyle,
+}
 
 impl Plugin for BoxFramePlugin {
-    fn build(&self, app: &mut bevy::prelude::App) {
+    fn build(&self, app: &mut App) {
         load_internal_asset!(
             app,
             SHADER_HANDLE,
@@ -34,9 +88,74 @@ impl Plugin for BoxFramePlugin {
         );
 
         app.add_plugins(MaterialPlugin::<SolidColorMaterial>::default())
-            .add_systems(PreUpdate, box_frame_backend.in_set(PickSet::Backend))
-            .add_systems(Update, (handle_visibility, highlight_handles))
+            .init_resource::<ActiveSnaps>()
+            .add_event::<BoxFrameBlocked>()
+            .add_event::<BoxFrameDivided>()
+            .add_event::<BoxFramesMerged>()
+            .init_resource::<BoxFrameBroadPhase>()
+            .init_resource::<BoxFrameOrigin>()
+            .add_systems(
+                PreUpdate,
+                (update_broad_phase, box_frame_backend)
+                    .chain()
+                    .in_set(PickSet::Backend),
+            )
+            .add_systems(
+                Update,
+                (
+                    (
+                        apply_theme.run_if(resource_exists_and_changed::<BoxFrameTheme>),
+                        handle_visibility,
+                        update_selection_visuals,
+                        apply_interaction_mode,
+                    )
+                        .chain(),
+                    highlight_handles,
+                ),
+            )
             // Correct highlighting updates depend on the state of dragging.
-            .add_systems(Update, (drag_face, highlight_face).chain());
+            .add_systems(
+                Update,
+                (
+                    promote_batched_frames,
+                    animate_faces,
+                    (drag_face, drag_slice),
+                    (highlight_face, draw_snap_indicators, sync_precise_faces),
+                    update_slice_planes,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_box_frame_batch.after(VisibilitySystems::VisibilityPropagate),
+                    update_grid_lines,
+                )
+                    .after(TransformSystem::TransformPropagate),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let world = app.world_mut();
+        if world.contains_resource::<BoxFrameTheme>() {
+            return;
+        }
+        assert!(
+            world.contains_resource::<Assets<PolylineMaterial>>(),
+            "BoxFramePlugin requires bevy_polyline::PolylinePlugin"
+        );
+        let visuals =
+            world.resource_scope(|world, mut line_materials: Mut<Assets<PolylineMaterial>>| {
+                world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+                    let mut materials = world.resource_mut::<Assets<SolidColorMaterial>>();
+                    BoxFrameVisuals::from_style(
+                        &self.style,
+                        &mut line_materials,
+                        &mut meshes,
+                        &mut materials,
+                    )
+                })
+            });
+        world.insert_resource(BoxFrameTheme(visuals));
     }
 }
@@ -1,32 +1,58 @@
-use crate::BoxFrame;
+use crate::{
+    box_edges, broad_phase::BoxFrameBroadPhase, selection::selection_allows_editing,
+    slice::slice_quad, BoxFrame, BoxFrameSelection, FaceIndex, Selected, FACE_NORMALS,
+};
 use bevy::{
     ecs::prelude::*,
-    math::Vec3Swizzles,
+    math::{FloatOrd, Vec3Swizzles},
     picking::backend::{ray::RayMap, HitData, PointerHits},
-    prelude::{Camera, GlobalTransform},
+    prelude::{Camera, GlobalTransform, Ray3d, Vec2, Vec3},
     render::view::RenderLayers,
 };
 use parry3d::{na::Isometry3, query::RayCast};
 
+/// Faces whose normal is closer than this cosine to perpendicular to a pointer
+/// ray are seen nearly edge-on, so edges near the pointer are picked instead.
+const EDGE_ON_COSINE: f32 = 0.1;
+
 /// Generates pointer hits for the box frame's AABB and handles.
+#[allow(clippy::type_complexity)]
 pub(crate) fn box_frame_backend(
     ray_map: Res<RayMap>,
-    cameras: Query<(&Camera, Option<&RenderLayers>)>,
-    box_frames: Query<(Entity, &BoxFrame, &GlobalTransform, Option<&RenderLayers>)>,
+    broad_phase: Res<BoxFrameBroadPhase>,
+    selection: Option<Res<BoxFrameSelection>>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    box_frames: Query<(
+        Entity,
+        &BoxFrame,
+        &GlobalTransform,
+        Option<&RenderLayers>,
+        Has<Selected>,
+    )>,
     transforms: Query<&GlobalTransform>,
     mut picking_out: EventWriter<PointerHits>,
 ) {
-    for (&ray_id, &ray) in ray_map.map.iter() {
-        let Ok((camera, view_mask)) = cameras.get(ray_id.camera) else {
+    for (&ray_id, &world_ray) in ray_map.map.iter() {
+        let Ok((camera, camera_transform, view_mask)) = cameras.get(ray_id.camera) else {
             continue;
         };
 
         let cam_view_mask = view_mask.unwrap_or_default();
 
-        let ray = parry3d::query::Ray::new(ray.origin.into(), ray.direction.xyz().into());
+        let ray =
+            parry3d::query::Ray::new(world_ray.origin.into(), world_ray.direction.xyz().into());
 
         let mut picks = Vec::new();
-        for (frame_entity, frame, frame_transform, frame_view_mask) in &box_frames {
+        for candidate in broad_phase.candidates(world_ray, camera, camera_transform) {
+            let Ok((frame_entity, frame, frame_transform, frame_view_mask, selected)) =
+                box_frames.get(candidate)
+            else {
+                continue;
+            };
+            if !frame.interaction.is_pickable() {
+                continue;
+            }
+
             let frame_view_mask = frame_view_mask.unwrap_or_default();
             if !frame_view_mask.intersects(cam_view_mask) {
                 continue;
@@ -34,14 +60,29 @@ pub(crate) fn box_frame_backend(
 
             let world_frame_center = frame_transform.transform_point(frame.center());
             // Check handle intersections first, they always take priority.
-            let ball = frame.handle_ball();
+            // Handles of faces that can't be dragged are never shown, so they
+            // aren't pickable either.
+            let editable = selection_allows_editing(selection.as_deref(), selected);
             if let Some((toi, handle_entity, world_handle_center)) = frame
                 .handle_entities()
                 .into_iter()
-                .filter_map(|handle_entity| {
+                .enumerate()
+                .filter(|&(face, _)| editable && frame.can_drag_face(face))
+                .filter_map(|(_, handle_entity)| {
                     let transform = transforms.get(handle_entity).ok()?;
-                    let isometry = isometry_from_transform(transform);
-                    ball.cast_ray(&isometry, &ray, f32::INFINITY, true)
+                    // The time of impact is the same in the handle's local
+                    // space, because the ray direction isn't normalized.
+                    let world_to_local = transform.affine().inverse();
+                    let local_ray = parry3d::query::Ray::new(
+                        world_to_local.transform_point3(world_ray.origin).into(),
+                        world_to_local
+                            .transform_vector3(*world_ray.direction)
+                            .into(),
+                    );
+                    frame
+                        .visuals
+                        .handle_shape
+                        .cast_local_ray(&local_ray)
                         .map(|toi| {
                             let world_handle_center = transform.translation();
                             (toi, handle_entity, world_handle_center)
@@ -70,13 +111,65 @@ pub(crate) fn box_frame_backend(
                 continue;
             }
 
-            // No handle intersections, check for AABB intersection.
+            // Then section plane outlines, which are on top of the faces.
+            if editable && frame.interaction.is_draggable() {
+                if let Some((toi, position, slice_entity)) =
+                    pick_slice(frame, frame_transform, camera, camera_transform, world_ray)
+                {
+                    picks.push((
+                        slice_entity,
+                        HitData::new(ray_id.camera, toi, Some(position), None),
+                    ));
+                    continue;
+                }
+            }
+
+            // No handle intersections. From inside the box, pick the face
+            // that the ray exits through.
             let isometry = isometry_from_transform(frame_transform);
-            if let Some(hit) =
-                frame
-                    .aabb()
-                    .cast_ray_and_get_normal(&isometry, &ray, f32::INFINITY, true)
-            {
+            let aabb = frame.aabb();
+            let inside = aabb.contains_local_point(&isometry.inverse_transform_point(&ray.origin));
+            if inside {
+                if let Some(hit) =
+                    aabb.cast_ray_and_get_normal(&isometry, &ray, f32::INFINITY, false)
+                {
+                    // Use the outward normal of the exit face.
+                    let normal = Vec3::from(hit.normal);
+                    let normal = if normal.dot(*world_ray.direction) < 0.0 {
+                        -normal
+                    } else {
+                        normal
+                    };
+                    picks.push((
+                        frame_entity,
+                        HitData::new(
+                            ray_id.camera,
+                            hit.time_of_impact,
+                            Some(ray.point_at(hit.time_of_impact).into()),
+                            Some(normal),
+                        ),
+                    ));
+                }
+                continue;
+            }
+
+            // Edges near the pointer are only picked when the ray misses the
+            // AABB, or hits a face that is seen nearly edge-on.
+            let aabb_hit = aabb.cast_ray_and_get_normal(&isometry, &ray, f32::INFINITY, true);
+            if aabb_hit.is_none_or(|hit| {
+                Vec3::from(hit.normal).dot(*world_ray.direction).abs() < EDGE_ON_COSINE
+            }) {
+                if let Some((toi, position, normal)) =
+                    pick_edge(frame, frame_transform, camera, camera_transform, world_ray)
+                {
+                    picks.push((
+                        frame_entity,
+                        HitData::new(ray_id.camera, toi, Some(position), Some(normal)),
+                    ));
+                    continue;
+                }
+            }
+            if let Some(hit) = aabb_hit {
                 picks.push((
                     frame_entity,
                     HitData::new(
@@ -99,7 +192,137 @@ pub(crate) fn box_frame_backend(
     }
 }
 
-fn isometry_from_transform(tfm: &GlobalTransform) -> Isometry3<f32> {
+/// Finds the edge closest to `ray` on screen, within the frame's
+/// [`edge_pick_tolerance`](BoxFrame::edge_pick_tolerance).
+///
+/// Returns the time of impact, world position and world normal of the hit.
+fn pick_edge(
+    frame: &BoxFrame,
+    frame_transform: &GlobalTransform,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    ray: Ray3d,
+) -> Option<(f32, Vec3, Vec3)> {
+    if frame.edge_pick_tolerance <= 0.0 {
+        return None;
+    }
+    let to_screen = |p: Vec3| camera.world_to_viewport(camera_transform, p).ok();
+    let pointer = to_screen(ray.get_point(1.0))?;
+
+    let rotation = frame_transform.rotation();
+    let world_normal = |face: FaceIndex| rotation * FACE_NORMALS[face];
+    let mut closest = None;
+    let mut closest_distance = frame.edge_pick_tolerance;
+    for (corners, faces) in box_edges(frame.faces()) {
+        // Edges behind the box would steal picks from the faces in front.
+        if faces
+            .iter()
+            .all(|&face| world_normal(face).dot(*ray.direction) >= 0.0)
+        {
+            continue;
+        }
+        let [a, b] = corners.map(|c| frame_transform.transform_point(c));
+        let (Some(screen_a), Some(screen_b)) = (to_screen(a), to_screen(b)) else {
+            continue;
+        };
+        let distance = distance_to_segment(pointer, screen_a, screen_b);
+        if distance <= closest_distance {
+            closest_distance = distance;
+            closest = Some(([a, b], faces));
+        }
+    }
+    let ([a, b], faces) = closest?;
+
+    let position = closest_point_to_ray(a, b, ray);
+    let toi = (position - ray.origin).dot(*ray.direction);
+    if toi < 0.0 {
+        return None;
+    }
+    let normal = faces
+        .map(world_normal)
+        .into_iter()
+        .min_by_key(|normal| FloatOrd(normal.dot(*ray.direction).abs()))?;
+    Some((toi, position, normal))
+}
+
+/// Finds the section plane outline closest to `ray` on screen, within the
+/// frame's [`edge_pick_tolerance`](BoxFrame::edge_pick_tolerance).
+///
+/// Returns the time of impact, world position and entity of the plane.
+fn pick_slice(
+    frame: &BoxFrame,
+    frame_transform: &GlobalTransform,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    ray: Ray3d,
+) -> Option<(f32, Vec3, Entity)> {
+    if frame.edge_pick_tolerance <= 0.0 {
+        return None;
+    }
+    let to_screen = |p: Vec3| camera.world_to_viewport(camera_transform, p).ok();
+    let pointer = to_screen(ray.get_point(1.0))?;
+
+    let rotation = frame_transform.rotation();
+    let mut closest = None;
+    let mut closest_distance = frame.edge_pick_tolerance;
+    for (axis, slice_entity) in frame.slice_entities.into_iter().enumerate() {
+        let Some(coord) = frame.slices[axis].filter(|_| slice_entity != Entity::PLACEHOLDER) else {
+            continue;
+        };
+        let quad = slice_quad(frame.faces(), axis, coord);
+        let center = quad.iter().sum::<Vec3>() / 4.0;
+        for i in 0..4 {
+            let [a, b] = [quad[i], quad[(i + 1) % 4]];
+            // Each side lies on a face, and sides on faces that point away
+            // from the camera are hidden behind the box.
+            let outward = (a + b) / 2.0 - center;
+            if (rotation * outward).dot(*ray.direction) >= 0.0 {
+                continue;
+            }
+            let [a, b] = [a, b].map(|c| frame_transform.transform_point(c));
+            let (Some(screen_a), Some(screen_b)) = (to_screen(a), to_screen(b)) else {
+                continue;
+            };
+            let distance = distance_to_segment(pointer, screen_a, screen_b);
+            if distance <= closest_distance {
+                closest_distance = distance;
+                closest = Some(([a, b], slice_entity));
+            }
+        }
+    }
+    let ([a, b], slice_entity) = closest?;
+
+    let position = closest_point_to_ray(a, b, ray);
+    let toi = (position - ray.origin).dot(*ray.direction);
+    (toi >= 0.0).then_some((toi, position, slice_entity))
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 0.0 {
+        ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance(a + t * ab)
+}
+
+/// The point on the segment from `a` to `b` that is closest to `ray`.
+fn closest_point_to_ray(a: Vec3, b: Vec3, ray: Ray3d) -> Vec3 {
+    let ab = b - a;
+    let dir = *ray.direction;
+    let r = a - ray.origin;
+    let (ab_ab, ab_dir) = (ab.dot(ab), ab.dot(dir));
+    let denom = ab_ab - ab_dir * ab_dir;
+    let s = if denom > f32::EPSILON {
+        ((ab_dir * dir.dot(r) - ab.dot(r)) / denom).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    a + s * ab
+}
+
+pub(crate) fn isometry_from_transform(tfm: &GlobalTransform) -> Isometry3<f32> {
     let (_scale, rot, trans) = tfm.to_scale_rotation_translation();
     Isometry3::from_parts(trans.into(), rot.into())
 }
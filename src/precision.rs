@@ -0,0 +1,177 @@
+use crate::{box_frame::BoxFrameHandle, BoxFrame};
+use bevy::{math::DVec3, prelude::*};
+use bevy_polyline::prelude::{Polyline, PolylineHandle};
+
+/// The high-precision coordinates of the world origin, for frames with
+/// [`PreciseFaces`].
+///
+/// Keep this near the camera so that world coordinates, which are `f32`, stay
+/// small enough to be precise. Changing it moves every frame with
+/// [`PreciseFaces`] by the opposite amount.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoxFrameOrigin(pub DVec3);
+
+impl BoxFrameOrigin {
+    /// Converts a high-precision point to world coordinates.
+    pub fn to_world(&self, point: DVec3) -> Vec3 {
+        (point - self.0).as_vec3()
+    }
+
+    /// Converts a point in world coordinates to high precision.
+    pub fn from_world(&self, point: Vec3) -> DVec3 {
+        self.0 + point.as_dvec3()
+    }
+}
+
+/// The faces of a [`BoxFrame`] in high-precision coordinates, e.g. for
+/// frames millions of meters away from the origin.
+///
+/// Like [`BoxFrame::faces`], these are coordinates along the frame's local
+/// axes, but relative to the high-precision origin instead of the frame's
+/// translation. The frame's `f32` faces are kept small by centering them on
+/// its [`Transform`], whose translation is derived from these faces, the
+/// [`BoxFrameOrigin`] and the transform's rotation and scale.
+///
+/// Dragging a face only changes that face, by the dragged distance relative to
+/// the frame's center, so other faces keep their exact coordinates.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::math::DVec3;
+/// # use bevy_fsl_box_frame::{BoxFrame, BoxFrameOrigin, PreciseFaces};
+/// fn spawn_block(mut commands: Commands) {
+///     commands.insert_resource(BoxFrameOrigin(DVec3::new(500_000.0, 0.0, 6_000_000.0)));
+///     commands.spawn((
+///         BoxFrame::new([0.0; 6]),
+///         PreciseFaces::new([500_010.0, 0.0, 6_000_010.0, 500_020.0, 5.0, 6_000_020.0]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct PreciseFaces {
+    faces: [f64; 6],
+    /// The high-precision coordinates of the frame's translation, along its
+    /// local axes.
+    center: DVec3,
+}
+
+impl PreciseFaces {
+    /// Frame faces with the given high-precision coordinates. See
+    /// [`FaceIndex`](crate::FaceIndex).
+    pub fn new(faces: [f64; 6]) -> Self {
+        let faces = sorted_faces(faces);
+        Self {
+            faces,
+            center: center(faces),
+        }
+    }
+
+    /// The high-precision coordinates of each face.
+    pub fn faces(&self) -> [f64; 6] {
+        self.faces
+    }
+
+    /// Moves the faces to the given high-precision coordinates.
+    pub fn set_faces(&mut self, faces: [f64; 6]) {
+        self.faces = sorted_faces(faces);
+    }
+
+    /// The high-precision coordinates of the frame's translation, which its
+    /// `f32` faces are relative to.
+    pub(crate) fn center(&self) -> DVec3 {
+        self.center
+    }
+
+    /// The faces relative to `center`, in `f32`.
+    fn local_faces(&self) -> [f32; 6] {
+        let center = self.center.to_array();
+        let mut local = [0.0; 6];
+        for (face, coord) in local.iter_mut().enumerate() {
+            *coord = (self.faces[face] - center[face % 3]) as f32;
+        }
+        local
+    }
+}
+
+/// Keeps [`BoxFrame`] faces and transforms in sync with [`PreciseFaces`], in
+/// both directions.
+#[allow(clippy::type_complexity)]
+pub(crate) fn sync_precise_faces(
+    origin: Res<BoxFrameOrigin>,
+    mut box_frames: Query<
+        (&mut BoxFrame, &mut PreciseFaces, &mut Transform),
+        Without<BoxFrameHandle>,
+    >,
+    mut handles: Query<(&mut BoxFrameHandle, &mut Transform)>,
+    line_handles: Query<&PolylineHandle>,
+    mut polylines: ResMut<Assets<Polyline>>,
+) {
+    for (mut frame, mut precise, mut transform) in &mut box_frames {
+        let precise_changed = precise.is_changed();
+        if !precise_changed
+            && !frame.is_changed()
+            && !transform.is_changed()
+            && !origin.is_changed()
+        {
+            continue;
+        }
+
+        // Faces moved by dragging.
+        if frame.is_changed() && !precise_changed {
+            let precise = precise.bypass_change_detection();
+            let center = precise.center.to_array();
+            let expected = precise.local_faces();
+            for (face, coord) in frame.faces().into_iter().enumerate() {
+                if coord != expected[face] {
+                    precise.faces[face] = center[face % 3] + f64::from(coord);
+                }
+            }
+        }
+
+        // Re-center the frame once it isn't dragged or animated.
+        if frame.dragging_face.is_none() && !frame.is_animating() {
+            let center = center(precise.faces);
+            if center != precise.center {
+                // Section planes stay put relative to the faces.
+                let shift = (center - precise.center).as_vec3();
+                for (axis, slice) in frame.slices.iter_mut().enumerate() {
+                    if let Some(coord) = slice {
+                        *coord -= shift[axis];
+                    }
+                }
+                precise.bypass_change_detection().center = center;
+            }
+            let local = precise.local_faces();
+            if frame.faces() != local {
+                frame.set_faces(local);
+                frame.transform_handles(&mut handles);
+                frame.reset_lines(&line_handles, &mut polylines);
+            }
+        }
+
+        let translation = (transform.rotation.as_dquat()
+            * (transform.scale.as_dvec3() * precise.center)
+            - origin.0)
+            .as_vec3();
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
+fn sorted_faces(faces: [f64; 6]) -> [f64; 6] {
+    let [x1, y1, z1, x2, y2, z2] = faces;
+    [
+        x1.min(x2),
+        y1.min(y2),
+        z1.min(z2),
+        x1.max(x2),
+        y1.max(y2),
+        z1.max(z2),
+    ]
+}
+
+fn center(faces: [f64; 6]) -> DVec3 {
+    let [x1, y1, z1, x2, y2, z2] = faces;
+    0.5 * DVec3::new(x1 + x2, y1 + y2, z1 + z2)
+}
@@ -0,0 +1,170 @@
+use crate::{BoxFrame, BoxFrameHandle, GroupDragMode};
+use bevy::{
+    picking::pointer::PointerId,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use bevy_polyline::prelude::PolylineMaterialHandle;
+
+/// Marks a [`BoxFrame`] as selected.
+///
+/// This is inserted and removed by [`BoxFrameSelectionPlugin`] in response to
+/// clicks, but it may also be inserted or removed by users.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Selected;
+
+/// Configuration for [`BoxFrameSelectionPlugin`].
+///
+/// While this resource exists, only [`Selected`] frames show handles and
+/// highlights, and only [`Selected`] frames can be dragged.
+#[derive(Resource, Clone, Debug)]
+pub struct BoxFrameSelection {
+    /// The button that selects a frame when clicked.
+    pub select_button: PointerButton,
+    /// While any of these keys is held, clicking a frame toggles whether it is
+    /// selected instead of replacing the selection.
+    pub add_modifiers: Vec<KeyCode>,
+    /// Whether clicking something other than a box frame clears the selection.
+    pub deselect_on_miss: bool,
+    /// When set, dragging a face of a selected frame also drags the equivalent
+    /// face of every other selected frame.
+    pub group_edit: Option<GroupDragMode>,
+}
+
+impl Default for BoxFrameSelection {
+    fn default() -> Self {
+        Self {
+            select_button: PointerButton::Primary,
+            add_modifiers: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            deselect_on_miss: true,
+            group_edit: None,
+        }
+    }
+}
+
+/// Lets users select [`BoxFrame`]s by clicking them.
+///
+/// Requires [`BoxFramePlugin`](crate::BoxFramePlugin). Configured by the
+/// [`BoxFrameSelection`] resource.
+pub struct BoxFrameSelectionPlugin;
+
+impl Plugin for BoxFrameSelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoxFrameSelection>()
+            .add_systems(Update, select_on_click.before(update_selection_visuals));
+    }
+}
+
+/// Whether a frame should show handles and highlights and accept drags.
+///
+/// Without a [`BoxFrameSelection`], every frame is editable.
+pub(crate) fn selection_allows_editing(
+    selection: Option<&BoxFrameSelection>,
+    selected: bool,
+) -> bool {
+    selection.is_none() || selected
+}
+
+#[allow(clippy::too_many_arguments)]
+fn select_on_click(
+    mut press_events: EventReader<Pointer<Pressed>>,
+    mut drag_start_events: EventReader<Pointer<DragStart>>,
+    mut click_events: EventReader<Pointer<Click>>,
+    mut dragging_pointers: Local<HashSet<PointerId>>,
+    selection: Res<BoxFrameSelection>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    box_frames: Query<Has<Selected>, With<BoxFrame>>,
+    selected_frames: Query<Entity, With<Selected>>,
+    mut commands: Commands,
+) {
+    // Releasing a drag also clicks the dragged entity, which mustn't change the
+    // selection.
+    for press in press_events.read() {
+        if press.event.button == selection.select_button {
+            dragging_pointers.remove(&press.pointer_id);
+        }
+    }
+    for drag_start in drag_start_events.read() {
+        if drag_start.event.button == selection.select_button {
+            dragging_pointers.insert(drag_start.pointer_id);
+        }
+    }
+
+    // A single release may click several entities, e.g. overlapping frames and
+    // the window behind them. Only the nearest frame under each pointer counts.
+    let mut nearest_clicks = HashMap::<PointerId, Option<(Entity, f32)>>::default();
+    for click in click_events.read() {
+        if click.event.button != selection.select_button
+            || dragging_pointers.contains(&click.pointer_id)
+        {
+            continue;
+        }
+        let nearest = nearest_clicks.entry(click.pointer_id).or_default();
+        if !box_frames.contains(click.target) {
+            continue;
+        }
+        let depth = click.event.hit.depth;
+        if nearest.is_none_or(|(_, nearest_depth)| depth < nearest_depth) {
+            *nearest = Some((click.target, depth));
+        }
+    }
+
+    let add_to_selection =
+        keys.is_some_and(|keys| keys.any_pressed(selection.add_modifiers.iter().copied()));
+    for nearest in nearest_clicks.into_values() {
+        let Some((clicked_frame, _)) = nearest else {
+            if selection.deselect_on_miss && !add_to_selection {
+                for entity in &selected_frames {
+                    commands.entity(entity).remove::<Selected>();
+                }
+            }
+            continue;
+        };
+
+        let Ok(is_selected) = box_frames.get(clicked_frame) else {
+            continue;
+        };
+        if add_to_selection {
+            if is_selected {
+                commands.entity(clicked_frame).remove::<Selected>();
+            } else {
+                commands.entity(clicked_frame).insert(Selected);
+            }
+        } else {
+            for entity in &selected_frames {
+                if entity != clicked_frame {
+                    commands.entity(entity).remove::<Selected>();
+                }
+            }
+            commands.entity(clicked_frame).insert(Selected);
+        }
+    }
+}
+
+/// Swaps edge materials and hides handles of deselected frames.
+pub(crate) fn update_selection_visuals(
+    selection: Option<Res<BoxFrameSelection>>,
+    newly_selected: Query<&BoxFrame, Added<Selected>>,
+    mut deselected: RemovedComponents<Selected>,
+    box_frames: Query<(&BoxFrame, Has<Selected>)>,
+    mut line_materials: Query<&mut PolylineMaterialHandle>,
+    mut handles: Query<&mut Visibility, With<BoxFrameHandle>>,
+) {
+    for frame in &newly_selected {
+        frame.clear_highlights(true, &mut line_materials);
+    }
+    for entity in deselected.read() {
+        let Ok((frame, selected)) = box_frames.get(entity) else {
+            continue;
+        };
+        frame.clear_highlights(selected, &mut line_materials);
+        if selection_allows_editing(selection.as_deref(), selected) {
+            continue;
+        }
+        for handle_entity in frame.handle_entities() {
+            if let Ok(mut vis) = handles.get_mut(handle_entity) {
+                *vis = Visibility::Hidden;
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    box_frame::sorted_faces, drag_face::closest_points_on_two_rays,
+    selection::selection_allows_editing, BoxFrame, BoxFrameSelection, InteractionMode, Selected,
+    FACE_NORMALS,
+};
+use bevy::{
+    picking::backend::ray::{RayId, RayMap},
+    prelude::*,
+};
+use bevy_polyline::prelude::{Polyline, PolylineBundle, PolylineHandle, PolylineMaterialHandle};
+
+/// The polyline of an interior section plane of a [`BoxFrame`].
+#[derive(Component)]
+pub(crate) struct BoxFrameSlice {
+    frame: Entity,
+    axis: usize,
+}
+
+// This data is constant while dragging is occurring.
+#[derive(Clone, Copy)]
+pub(crate) struct SliceDragging {
+    ray_id: RayId,
+    axis: usize,
+    // The plane's coordinate at time of DragStart.
+    initial_coord: f32,
+    // The ray along which the plane is translated during dragging. In world
+    // coordinates.
+    drag_ray: Ray3d,
+}
+
+/// The corners of the section plane at `coord` along `axis`, in local
+/// coordinates.
+pub(crate) fn slice_quad(faces: [f32; 6], axis: usize, coord: f32) -> [Vec3; 4] {
+    let [x1, y1, z1, x2, y2, z2] = sorted_faces(faces);
+    let (mins, maxs) = (Vec3::new(x1, y1, z1), Vec3::new(x2, y2, z2));
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    [(false, false), (true, false), (true, true), (false, true)].map(|(max_u, max_v)| {
+        let mut corner = Vec3::ZERO;
+        corner[axis] = coord;
+        corner[u] = if max_u { maxs[u] } else { mins[u] };
+        corner[v] = if max_v { maxs[v] } else { mins[v] };
+        corner
+    })
+}
+
+/// Clamps section planes between their faces, and spawns, updates or despawns
+/// their polylines to match.
+pub(crate) fn update_slice_planes(
+    mut commands: Commands,
+    mut box_frames: Query<(Entity, &mut BoxFrame), Changed<BoxFrame>>,
+    line_handles: Query<&PolylineHandle>,
+    mut line_materials: Query<&mut PolylineMaterialHandle>,
+    mut visibility: Query<&mut Visibility>,
+    mut polylines: ResMut<Assets<Polyline>>,
+) {
+    for (entity, mut frame) in &mut box_frames {
+        let faces = sorted_faces(frame.faces());
+        let clamped = std::array::from_fn(|axis| {
+            frame.slices[axis].map(|coord| coord.clamp(faces[axis], faces[axis + 3]))
+        });
+        if frame.slices != clamped {
+            frame.slices = clamped;
+        }
+        if !frame.has_child_entities() {
+            continue;
+        }
+        let frame_visibility = if frame.interaction == InteractionMode::Hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+
+        for axis in 0..3 {
+            let slice_entity = frame.slice_entities[axis];
+            let Some(coord) = frame.slices[axis] else {
+                if slice_entity != Entity::PLACEHOLDER {
+                    if let Ok(mut slice) = commands.get_entity(slice_entity) {
+                        slice.try_despawn();
+                    }
+                    frame.slice_entities[axis] = Entity::PLACEHOLDER;
+                }
+                continue;
+            };
+            let [a, b, c, d] = slice_quad(faces, axis, coord);
+            let vertices = vec![a, b, c, d, a];
+            let material = frame.visuals.highlight_material(axis + 3).clone();
+
+            if slice_entity == Entity::PLACEHOLDER {
+                frame.slice_entities[axis] = commands
+                    .spawn((
+                        PolylineBundle {
+                            polyline: PolylineHandle(polylines.add(Polyline { vertices })),
+                            material,
+                            visibility: frame_visibility,
+                            ..default()
+                        },
+                        BoxFrameSlice {
+                            frame: entity,
+                            axis,
+                        },
+                        Pickable {
+                            should_block_lower: false,
+                            is_hoverable: true,
+                        },
+                        ChildOf(entity),
+                    ))
+                    .id();
+                continue;
+            }
+
+            if let Ok(line_handle) = line_handles.get(slice_entity) {
+                if polylines
+                    .get(&line_handle.0)
+                    .is_some_and(|line| line.vertices != vertices)
+                {
+                    if let Some(line) = polylines.get_mut(&line_handle.0) {
+                        line.vertices = vertices;
+                    }
+                }
+            }
+            if let Ok(mut line_material) = line_materials.get_mut(slice_entity) {
+                if line_material.0 != material.0 {
+                    *line_material = material;
+                }
+            }
+            if let Ok(mut vis) = visibility.get_mut(slice_entity) {
+                vis.set_if_neq(frame_visibility);
+            }
+        }
+    }
+}
+
+/// Drags section planes by their outlines, like faces.
+#[allow(clippy::type_complexity)]
+pub(crate) fn drag_slice(
+    mut drag_start_events: EventReader<Pointer<DragStart>>,
+    mut drag_end_events: EventReader<Pointer<DragEnd>>,
+    ray_map: Res<RayMap>,
+    selection: Option<Res<BoxFrameSelection>>,
+    slices: Query<&BoxFrameSlice>,
+    mut box_frames: Query<(&mut BoxFrame, &GlobalTransform, Has<Selected>)>,
+) {
+    for drag_start in drag_start_events.read() {
+        let Ok(slice) = slices.get(drag_start.target) else {
+            continue;
+        };
+        let Ok((mut frame, transform, selected)) = box_frames.get_mut(slice.frame) else {
+            continue;
+        };
+        if !selection_allows_editing(selection.as_deref(), selected)
+            || !frame.interaction.is_draggable()
+            || drag_start.event.button != frame.drag_button
+        {
+            continue;
+        }
+        let (Some(world_position), Some(initial_coord)) =
+            (drag_start.event.hit.position, frame.slices[slice.axis])
+        else {
+            continue;
+        };
+        let Ok(direction) = Dir3::new(transform.rotation() * FACE_NORMALS[slice.axis + 3]) else {
+            continue;
+        };
+        frame.dragging_slice = Some(SliceDragging {
+            ray_id: RayId::new(drag_start.event.hit.camera, drag_start.pointer_id),
+            axis: slice.axis,
+            initial_coord,
+            drag_ray: Ray3d {
+                origin: world_position,
+                direction,
+            },
+        });
+    }
+    for drag_end in drag_end_events.read() {
+        let Ok(slice) = slices.get(drag_end.target) else {
+            continue;
+        };
+        if let Ok((mut frame, ..)) = box_frames.get_mut(slice.frame) {
+            frame.dragging_slice = None;
+        }
+    }
+
+    for (mut frame, _, selected) in &mut box_frames {
+        let Some(SliceDragging {
+            ray_id,
+            axis,
+            initial_coord,
+            drag_ray,
+        }) = frame.dragging_slice
+        else {
+            continue;
+        };
+        // The frame may have been locked or deselected after the drag started.
+        if !frame.interaction.is_draggable()
+            || !selection_allows_editing(selection.as_deref(), selected)
+        {
+            frame.dragging_slice = None;
+            continue;
+        }
+        let Some(pointer_ray) = ray_map.map.get(&ray_id) else {
+            continue;
+        };
+        let Some((drag_delta, _)) = closest_points_on_two_rays(&drag_ray, pointer_ray) else {
+            continue;
+        };
+        // NOTE: Assumes drag_ray is a unit vector.
+        let faces = frame.sorted_faces();
+        let coord = (initial_coord + drag_delta).clamp(faces[axis], faces[axis + 3]);
+        if frame.slices[axis] != Some(coord) {
+            frame.slices[axis] = Some(coord);
+        }
+    }
+}
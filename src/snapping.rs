@@ -0,0 +1,291 @@
+use crate::{face_centers, BoxFrame, FACE_NORMALS};
+use bevy::{
+    ecs::system::SystemParam,
+    picking::backend::ray::RayId,
+    platform::collections::HashMap,
+    prelude::*,
+    render::{mesh::VertexAttributeValues, primitives::Aabb},
+};
+use bevy_polyline::prelude::{Polyline, PolylineBundle, PolylineHandle};
+
+/// Enables magnetic snapping of dragged faces.
+///
+/// While this resource exists, a dragged face snaps to the nearest target
+/// within [`threshold_pixels`](Self::threshold_pixels) of it on screen, and an
+/// indicator is drawn around the snap target.
+#[derive(Resource, Clone, Debug)]
+pub struct BoxFrameSnapping {
+    /// Faces snap to targets closer than this distance, in logical pixels.
+    pub threshold_pixels: f32,
+    /// Whether faces snap to parallel faces of other [`BoxFrame`]s.
+    pub snap_to_frames: bool,
+    /// Whether faces snap to entities with a [`SnapTarget`] component.
+    pub snap_to_targets: bool,
+    /// The height of a horizontal ground plane that faces snap to when they are
+    /// parallel to it.
+    pub ground_plane: Option<f32>,
+}
+
+impl Default for BoxFrameSnapping {
+    fn default() -> Self {
+        Self {
+            threshold_pixels: 10.0,
+            snap_to_frames: true,
+            snap_to_targets: true,
+            ground_plane: Some(0.0),
+        }
+    }
+}
+
+/// Marks scene geometry that dragged faces snap to.
+///
+/// Only used while [`BoxFrameSnapping::snap_to_targets`] is enabled.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SnapTarget {
+    /// Snap to the planes of the entity's [`Aabb`].
+    #[default]
+    Aabb,
+    /// Snap to the vertices of the entity's [`Mesh3d`].
+    Vertices,
+}
+
+/// A point that a dragged face snapped to.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SnapHit {
+    /// The snap target in world coordinates.
+    point: Vec3,
+    /// The direction that the face is dragged in.
+    normal: Dir3,
+    /// Half the size of the indicator in world units.
+    radius: f32,
+}
+
+/// Snap targets found during the last update, keyed by the dragged frame.
+#[derive(Resource, Default)]
+pub(crate) struct ActiveSnaps {
+    hits: HashMap<Entity, SnapHit>,
+    indicators: HashMap<Entity, Entity>,
+}
+
+/// The world-space faces of a frame that might be a snap target.
+pub(crate) struct FrameSnapshot {
+    entity: Entity,
+    dragging_ray: Option<RayId>,
+    face_centers: [Vec3; 6],
+    face_normals: [Vec3; 6],
+}
+
+impl FrameSnapshot {
+    pub fn new(entity: Entity, frame: &BoxFrame, transform: &GlobalTransform) -> Self {
+        let rotation = transform.rotation();
+        Self {
+            entity,
+            dragging_ray: frame.dragging_face.map(|d| d.ray_id()),
+            face_centers: face_centers(frame.faces()).map(|c| transform.transform_point(c)),
+            face_normals: FACE_NORMALS.map(|n| rotation * n),
+        }
+    }
+}
+
+/// Finds snap targets for dragged faces.
+#[derive(SystemParam)]
+pub(crate) struct FaceSnapper<'w, 's> {
+    settings: Option<Res<'w, BoxFrameSnapping>>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+    targets: Query<
+        'w,
+        's,
+        (
+            &'static SnapTarget,
+            &'static GlobalTransform,
+            Option<&'static Aabb>,
+            Option<&'static Mesh3d>,
+        ),
+    >,
+    meshes: Res<'w, Assets<Mesh>>,
+    active: ResMut<'w, ActiveSnaps>,
+}
+
+impl FaceSnapper<'_, '_> {
+    pub fn is_enabled(&self) -> bool {
+        self.settings.is_some()
+    }
+
+    /// Forgets the snap targets found during the last update.
+    pub fn clear(&mut self) {
+        self.active.hits.clear();
+    }
+
+    /// Returns the drag distance along `drag_ray` after snapping `delta` to the
+    /// nearest target, if any.
+    pub fn snap(
+        &mut self,
+        frame_entity: Entity,
+        ray_id: RayId,
+        drag_ray: Ray3d,
+        delta: f32,
+        frames: &[FrameSnapshot],
+    ) -> f32 {
+        let Some(settings) = self.settings.as_deref() else {
+            return delta;
+        };
+        let Ok((camera, camera_transform)) = self.cameras.get(ray_id.camera) else {
+            return delta;
+        };
+        let to_screen = |t: f32| {
+            camera
+                .world_to_viewport(camera_transform, drag_ray.get_point(t))
+                .ok()
+        };
+        let Some(face_screen) = to_screen(delta) else {
+            return delta;
+        };
+
+        let dir = *drag_ray.direction;
+        let parallel = |normal: Vec3| normal.dot(dir).abs() > PARALLEL_THRESHOLD;
+        let along_ray = |point: Vec3| (point - drag_ray.origin).dot(dir);
+
+        // Candidates are pairs of (drag distance, target point).
+        let mut candidates = Vec::new();
+        if settings.snap_to_frames {
+            for other in frames {
+                if other.entity == frame_entity || other.dragging_ray == Some(ray_id) {
+                    continue;
+                }
+                for (center, normal) in other.face_centers.into_iter().zip(other.face_normals) {
+                    if parallel(normal) {
+                        candidates.push((along_ray(center), center));
+                    }
+                }
+            }
+        }
+        if settings.snap_to_targets {
+            for (target, transform, aabb, mesh) in &self.targets {
+                match target {
+                    SnapTarget::Aabb => {
+                        let Some(aabb) = aabb else {
+                            continue;
+                        };
+                        let (min, max) = (Vec3::from(aabb.min()), Vec3::from(aabb.max()));
+                        for i in 0..8 {
+                            let corner = Vec3::select(
+                                BVec3::new(i & 1 != 0, i & 2 != 0, i & 4 != 0),
+                                max,
+                                min,
+                            );
+                            let world_corner = transform.transform_point(corner);
+                            candidates.push((along_ray(world_corner), world_corner));
+                        }
+                    }
+                    SnapTarget::Vertices => {
+                        let Some(VertexAttributeValues::Float32x3(positions)) = mesh
+                            .and_then(|m| self.meshes.get(&m.0))
+                            .and_then(|m| m.attribute(Mesh::ATTRIBUTE_POSITION))
+                        else {
+                            continue;
+                        };
+                        for &position in positions {
+                            let world_vertex = transform.transform_point(position.into());
+                            candidates.push((along_ray(world_vertex), world_vertex));
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(height) = settings.ground_plane {
+            if parallel(Vec3::Y) {
+                let t = (height - drag_ray.origin.y) / dir.y;
+                let face_point = drag_ray.get_point(delta);
+                candidates.push((t, face_point.with_y(height)));
+            }
+        }
+
+        let mut best: Option<(f32, f32, Vec3)> = None;
+        for (t, point) in candidates {
+            let Some(screen) = to_screen(t) else {
+                continue;
+            };
+            let distance = screen.distance(face_screen);
+            if distance <= settings.threshold_pixels
+                && best.is_none_or(|(best_distance, ..)| distance < best_distance)
+            {
+                best = Some((distance, t, point));
+            }
+        }
+        let Some((_, t, point)) = best else {
+            return delta;
+        };
+
+        // Size the indicator so it matches the snapping threshold on screen.
+        let radius = to_screen(t + 1.0)
+            .and_then(|p| to_screen(t).map(|q| p.distance(q)))
+            .filter(|&pixels_per_unit| pixels_per_unit > 0.0)
+            .map_or(1.0, |pixels_per_unit| {
+                settings.threshold_pixels / pixels_per_unit
+            });
+        self.active.hits.insert(
+            frame_entity,
+            SnapHit {
+                point,
+                normal: drag_ray.direction,
+                radius,
+            },
+        );
+        t
+    }
+}
+
+/// Faces are considered parallel if their normals are within about 2.5
+/// degrees.
+const PARALLEL_THRESHOLD: f32 = 0.999;
+
+/// Draws a square around the target of each active snap.
+pub(crate) fn draw_snap_indicators(
+    mut commands: Commands,
+    mut active: ResMut<ActiveSnaps>,
+    mut polylines: ResMut<Assets<Polyline>>,
+    box_frames: Query<&BoxFrame>,
+    line_handles: Query<&PolylineHandle>,
+) {
+    let ActiveSnaps { hits, indicators } = &mut *active;
+
+    // Frames that were removed while snapping lose their indicators too.
+    hits.retain(|&frame_entity, _| box_frames.contains(frame_entity));
+    indicators.retain(|frame_entity, indicator| {
+        let keep = hits.contains_key(frame_entity);
+        if !keep {
+            commands.entity(*indicator).despawn();
+        }
+        keep
+    });
+
+    for (&frame_entity, hit) in hits.iter() {
+        let Ok(frame) = box_frames.get(frame_entity) else {
+            continue;
+        };
+        let (u, v) = hit.normal.any_orthonormal_pair();
+        let (u, v) = (hit.radius * u, hit.radius * v);
+        let polyline = Polyline {
+            vertices: [u + v, u - v, -u - v, -u + v, u + v]
+                .map(|offset| hit.point + offset)
+                .to_vec(),
+        };
+
+        let existing = indicators
+            .get(&frame_entity)
+            .and_then(|&e| line_handles.get(e).ok())
+            .and_then(|h| polylines.get_mut(&h.0));
+        if let Some(existing) = existing {
+            *existing = polyline;
+            continue;
+        }
+        let indicator = commands
+            .spawn(PolylineBundle {
+                polyline: PolylineHandle(polylines.add(polyline)),
+                material: frame.visuals.edge_highlight_material.clone(),
+                ..default()
+            })
+            .id();
+        indicators.insert(frame_entity, indicator);
+    }
+}
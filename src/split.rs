@@ -0,0 +1,294 @@
+use crate::{box_frame::sorted_faces, duplicate::FrameCopy, BoxFrame, PreciseFaces};
+use bevy::{math::DVec3, prelude::*};
+
+/// How [`split_box_frame`](crate::BoxFrameCommandsExt::split_box_frame)
+/// divides a frame into parts along its local axes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoxFrameSplit {
+    /// Slabs between the given coordinates along `axis`, e.g. bench
+    /// elevations. Coordinates outside of the frame are ignored.
+    At {
+        /// The local axis, `0` for X, `1` for Y and `2` for Z.
+        axis: usize,
+        /// Local coordinates along `axis`.
+        coords: Vec<f32>,
+    },
+    /// Slabs of the given thickness along `axis`, starting at the minimum
+    /// face. The last slab is thinner if the frame isn't a whole number of
+    /// slabs thick.
+    Every {
+        /// The local axis, `0` for X, `1` for Y and `2` for Z.
+        axis: usize,
+        /// The thickness of each slab.
+        interval: f32,
+    },
+    /// Equal sub-boxes, with the given number along each local axis.
+    Grid(UVec3),
+}
+
+impl BoxFrameSplit {
+    /// The sorted faces of each part of a frame with the given faces, ordered
+    /// from the minimum corner along X, then Y, then Z.
+    pub fn parts(&self, faces: [f32; 6]) -> Vec<[f32; 6]> {
+        let [x1, y1, z1, x2, y2, z2] = sorted_faces(faces);
+        let mins = [x1, y1, z1];
+        let maxs = [x2, y2, z2];
+        // The coordinates that bound parts along each axis.
+        let mut bounds = [0, 1, 2].map(|axis| vec![mins[axis], maxs[axis]]);
+        match self {
+            Self::At { axis, coords } => {
+                let axis = axis % 3;
+                let mut cuts: Vec<_> = coords
+                    .iter()
+                    .copied()
+                    .filter(|&c| c > mins[axis] && c < maxs[axis])
+                    .collect();
+                cuts.sort_by(f32::total_cmp);
+                cuts.dedup();
+                bounds[axis] = [mins[axis]]
+                    .into_iter()
+                    .chain(cuts)
+                    .chain([maxs[axis]])
+                    .collect();
+            }
+            Self::Every { axis, interval } => {
+                let axis = axis % 3;
+                let extent = maxs[axis] - mins[axis];
+                if *interval > 0.0 && *interval < extent {
+                    // Cuts closer than this to the maximum face would leave
+                    // slivers from rounding errors.
+                    let epsilon = 1e-4 * interval;
+                    bounds[axis] = (0..)
+                        .map(|i| mins[axis] + i as f32 * interval)
+                        .take_while(|&c| c < maxs[axis] - epsilon)
+                        .chain([maxs[axis]])
+                        .collect();
+                }
+            }
+            Self::Grid(counts) => {
+                for axis in 0..3 {
+                    let count = counts[axis].max(1);
+                    bounds[axis] = (0..=count)
+                        .map(|i| {
+                            if i == count {
+                                maxs[axis]
+                            } else {
+                                mins[axis] + (maxs[axis] - mins[axis]) * i as f32 / count as f32
+                            }
+                        })
+                        .collect();
+                }
+            }
+        }
+
+        let mut parts = Vec::new();
+        for z in bounds[2].windows(2) {
+            for y in bounds[1].windows(2) {
+                for x in bounds[0].windows(2) {
+                    parts.push([x[0], y[0], z[0], x[1], y[1], z[1]]);
+                }
+            }
+        }
+        parts
+    }
+}
+
+/// Sent when a frame is split by
+/// [`split_box_frame`](crate::BoxFrameCommandsExt::split_box_frame).
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct BoxFrameDivided {
+    /// The frame that was split, which became the first part.
+    pub frame: Entity,
+    /// Every part, including `frame`, in the order of
+    /// [`BoxFrameSplit::parts`].
+    pub parts: Vec<Entity>,
+}
+
+/// Sent when frames are merged by
+/// [`merge_box_frames`](crate::BoxFrameCommandsExt::merge_box_frames).
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct BoxFramesMerged {
+    /// The frame that now covers all of the merged frames.
+    pub frame: Entity,
+    /// The frames that were merged into `frame` and despawned.
+    pub merged: Vec<Entity>,
+}
+
+/// Replaces the frame on `source` with the parts of `split`.
+pub(crate) fn split_frame(mut source: EntityWorldMut, split: &BoxFrameSplit) {
+    let Some(frame) = source.get::<BoxFrame>() else {
+        return;
+    };
+    let faces = frame.sorted_faces();
+    let parts = split.parts(faces);
+    if parts.len() < 2 {
+        return;
+    }
+    let frame = source.id();
+    let copies: Vec<_> = parts
+        .into_iter()
+        .filter_map(|part| {
+            let mut copy = FrameCopy::of(&source)?;
+            copy.set_faces(part);
+            for (axis, slice) in copy.frame.slices.iter_mut().enumerate() {
+                let is_last = part[axis + 3] == faces[axis + 3];
+                if slice.is_some_and(|coord| {
+                    coord < part[axis] || (coord >= part[axis + 3] && !is_last)
+                }) {
+                    *slice = None;
+                }
+            }
+            Some(copy)
+        })
+        .collect();
+    source.world_scope(|world| {
+        let parts = copies
+            .into_iter()
+            .enumerate()
+            .map(|(i, copy)| {
+                let entity = if i == 0 {
+                    frame
+                } else {
+                    world.spawn_empty().id()
+                };
+                copy.insert(world, entity);
+                entity
+            })
+            .collect();
+        world.send_event(BoxFrameDivided { frame, parts });
+    });
+}
+
+/// Merges the frames on `others` into the frame on `target`, if their union is
+/// a box along the same local axes.
+pub(crate) fn merge_frames(mut target: EntityWorldMut, others: &[Entity]) {
+    let Some(mut merged) = FrameCopy::of(&target) else {
+        return;
+    };
+    let frame = target.id();
+    let others: Vec<_> = others.iter().copied().filter(|&e| e != frame).collect();
+
+    let mut slices = local_slices(&merged);
+    let faces = target.world_scope(|world| {
+        let mut faces = vec![local_faces(&merged)];
+        for &other in &others {
+            let other = world.get_entity_mut(other).ok()?;
+            let other = FrameCopy::of(&other)?;
+            let other_faces = relative_faces(&merged, &other)?;
+            // Missing section planes come from the first frame that has one.
+            let offset = local_faces(&other)
+                .into_iter()
+                .zip(other_faces)
+                .map(|(local, relative)| relative - local);
+            for ((slice, other_slice), offset) in
+                slices.iter_mut().zip(local_slices(&other)).zip(offset)
+            {
+                if slice.is_none() {
+                    *slice = other_slice.map(|coord| coord + offset);
+                }
+            }
+            faces.push(other_faces);
+        }
+        Some(faces)
+    });
+    let Some(union) = faces.as_deref().and_then(merged_faces) else {
+        return;
+    };
+
+    let center = match &mut merged.precise {
+        // The frame's faces are recentered from its precise faces.
+        Some(precise) => {
+            *precise = PreciseFaces::new(union);
+            precise.center()
+        }
+        None => {
+            merged.frame.set_faces(union.map(|coord| coord as f32));
+            DVec3::ZERO
+        }
+    };
+    merged.frame.slices =
+        std::array::from_fn(|axis| slices[axis].map(|coord| (coord - center[axis]) as f32));
+    target.world_scope(|world| {
+        merged.insert(world, frame);
+        for &other in &others {
+            if let Ok(other) = world.get_entity_mut(other) {
+                other.despawn();
+            }
+        }
+        world.send_event(BoxFramesMerged {
+            frame,
+            merged: others,
+        });
+    });
+}
+
+/// The faces of `copy` in its own local coordinates.
+fn local_faces(copy: &FrameCopy) -> [f64; 6] {
+    match &copy.precise {
+        Some(precise) => precise.faces(),
+        None => copy.frame.sorted_faces().map(f64::from),
+    }
+}
+
+/// The section planes of `copy` in the coordinates of [`local_faces`].
+fn local_slices(copy: &FrameCopy) -> [Option<f64>; 3] {
+    let center = copy.precise.map_or(DVec3::ZERO, |precise| precise.center());
+    std::array::from_fn(|axis| copy.frame.slices[axis].map(|coord| center[axis] + f64::from(coord)))
+}
+
+/// The faces of `other` in the local coordinates of `target`, if they share
+/// local axes.
+fn relative_faces(target: &FrameCopy, other: &FrameCopy) -> Option<[f64; 6]> {
+    let [a, b] = [target, other].map(|copy| copy.transform);
+    if target.parent.as_ref().map(ChildOf::parent) != other.parent.as_ref().map(ChildOf::parent)
+        || target.precise.is_some() != other.precise.is_some()
+        || a.rotation.angle_between(b.rotation) > 1e-4
+        || !a
+            .scale
+            .abs_diff_eq(b.scale, 1e-5 * a.scale.abs().max_element())
+    {
+        return None;
+    }
+    let mut faces = local_faces(other);
+    // Precise faces share the origin already.
+    if target.precise.is_none() {
+        let offset = (a.rotation.inverse() * (b.translation - a.translation) / a.scale)
+            .as_dvec3()
+            .to_array();
+        for (face, coord) in faces.iter_mut().enumerate() {
+            *coord += offset[face % 3];
+        }
+    }
+    Some(faces)
+}
+
+/// The union of `boxes`, if it is a box that they fill without overlapping.
+fn merged_faces(boxes: &[[f64; 6]]) -> Option<[f64; 6]> {
+    let volume = |[x1, y1, z1, x2, y2, z2]: [f64; 6]| {
+        (x2 - x1).max(0.0) * (y2 - y1).max(0.0) * (z2 - z1).max(0.0)
+    };
+    let mut union = *boxes.first()?;
+    for b in boxes {
+        for axis in 0..3 {
+            union[axis] = union[axis].min(b[axis]);
+            union[axis + 3] = union[axis + 3].max(b[axis + 3]);
+        }
+    }
+    let tolerance = 1e-5 * volume(union);
+    for (i, a) in boxes.iter().enumerate() {
+        for b in &boxes[i + 1..] {
+            let intersection: [f64; 6] = std::array::from_fn(|f| {
+                if f < 3 {
+                    a[f].max(b[f])
+                } else {
+                    a[f].min(b[f])
+                }
+            });
+            if volume(intersection) > tolerance {
+                return None;
+            }
+        }
+    }
+    let total: f64 = boxes.iter().map(|&b| volume(b)).sum();
+    ((total - volume(union)).abs() <= tolerance).then_some(union)
+}
@@ -0,0 +1,348 @@
+//! A headless harness for testing [`BoxFrame`] interactions.
+//!
+//! [`BoxFrameTestApp`] builds an [`App`] with [`BoxFramePlugin`], a camera
+//! rendering to a virtual window, and a synthetic pointer. Tests move the
+//! pointer in screen space (or override its ray in the
+//! [`RayMap`]), press and release buttons, and then assert on the state of the
+//! frames.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_fsl_box_frame::testing::BoxFrameTestApp;
+//!
+//! let mut test = BoxFrameTestApp::new();
+//! let frame = test.spawn_frame([-0.5, -0.5, -0.5, 0.5, 0.5, 0.5], Transform::default());
+//!
+//! // Drag the +Y face up.
+//! test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+//! assert!((test.frame(frame).faces()[4] - 1.0).abs() < 1e-3);
+//! ```
+
+use crate::{
+    picking_backend::box_frame_backend, BoxFrame, BoxFramePlugin, BoxFrameVisuals, FaceIndex,
+    SolidColorMaterial,
+};
+use bevy::{
+    app::Plugins,
+    asset::uuid::Uuid,
+    ecs::system::RunSystemOnce,
+    input::InputPlugin,
+    picking::{
+        backend::{
+            ray::{RayId, RayMap},
+            PointerHits,
+        },
+        pointer::{Location, PointerAction, PointerId, PointerInput},
+        InteractionPlugin, PickSet, PickingPlugin,
+    },
+    prelude::*,
+    render::{
+        camera::{camera_system, ManualTextureViews, NormalizedRenderTarget},
+        mesh::MeshPlugin,
+        view::VisibilityPlugin,
+    },
+    window::{ExitCondition, PrimaryWindow, WindowResolution},
+};
+use bevy_polyline::prelude::{Polyline, PolylineMaterial, PolylineMaterialHandle};
+
+/// The identifier of the synthetic pointer.
+pub const TEST_POINTER: PointerId =
+    PointerId::Custom(Uuid::from_u128(0x5eb1_e27a_b0c5_4f4a_8e3b_7c1d_2f6a_9b80));
+
+/// The size of the virtual window in logical pixels.
+pub const TEST_WINDOW_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+
+/// The number of pointer moves used by [`BoxFrameTestApp::drag`].
+const DRAG_STEPS: usize = 4;
+
+/// Overrides the ray of the synthetic pointer in the [`RayMap`].
+#[derive(Resource, Default)]
+struct PointerRayOverride(Option<Ray3d>);
+
+/// An [`App`] for testing pointer interactions with [`BoxFrame`]s without a
+/// window or GPU.
+pub struct BoxFrameTestApp {
+    /// The app under test.
+    pub app: App,
+    camera: Entity,
+    pointer_position: Vec2,
+}
+
+impl Default for BoxFrameTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoxFrameTestApp {
+    /// Creates an app with [`BoxFramePlugin`] and a camera at `(3, 4, 5)`
+    /// looking at the origin, so the `+X`, `+Y` and `+Z` faces of a box at the
+    /// origin are visible.
+    pub fn new() -> Self {
+        Self::with_plugins(())
+    }
+
+    /// Like [`new`](Self::new), but also adds `plugins` to the app.
+    ///
+    /// Plugins can't be added to [`app`](Self::app) afterwards, because the
+    /// app has already been finished.
+    pub fn with_plugins<M>(plugins: impl Plugins<M>) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin::default(),
+            MeshPlugin,
+            VisibilityPlugin,
+            InputPlugin,
+            WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: WindowResolution::new(TEST_WINDOW_SIZE.x, TEST_WINDOW_SIZE.y)
+                        .with_scale_factor_override(1.0),
+                    ..default()
+                }),
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            },
+            PickingPlugin::default(),
+            InteractionPlugin,
+        ))
+        // These are normally provided by the render and polyline plugins.
+        .init_asset::<Shader>()
+        .init_asset::<Image>()
+        .init_asset::<Polyline>()
+        .init_asset::<PolylineMaterial>()
+        .init_resource::<ManualTextureViews>()
+        .init_resource::<PointerRayOverride>()
+        .add_plugins(BoxFramePlugin::default())
+        .add_plugins(plugins)
+        .add_systems(PostUpdate, camera_system)
+        .add_systems(
+            PreUpdate,
+            override_pointer_ray
+                .after(PickSet::ProcessInput)
+                .before(PickSet::Backend),
+        );
+
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Transform::from_xyz(3.0, 4.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ))
+            .id();
+        app.world_mut().spawn(TEST_POINTER);
+        app.finish();
+        app.cleanup();
+
+        let mut test = Self {
+            app,
+            camera,
+            pointer_position: Vec2::ZERO,
+        };
+        // Computes the camera's viewport and global transform.
+        test.update();
+        test
+    }
+
+    /// The camera entity.
+    pub fn camera(&self) -> Entity {
+        self.camera
+    }
+
+    /// Moves the camera, updating the app so the new transform takes effect.
+    pub fn set_camera_transform(&mut self, transform: Transform) {
+        self.app
+            .world_mut()
+            .entity_mut(self.camera)
+            .insert(transform);
+        self.update();
+    }
+
+    /// Creates default visuals for a frame.
+    pub fn default_visuals(&mut self) -> BoxFrameVisuals {
+        let world = self.app.world_mut();
+        world.resource_scope(|world, mut line_materials: Mut<Assets<PolylineMaterial>>| {
+            world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+                let mut materials = world.resource_mut::<Assets<SolidColorMaterial>>();
+                BoxFrameVisuals::new_default(&mut line_materials, &mut meshes, &mut materials)
+            })
+        })
+    }
+
+    /// Spawns a frame with default visuals, dragged by the primary button.
+    pub fn spawn_frame(&mut self, faces: [f32; 6], transform: Transform) -> Entity {
+        self.spawn((BoxFrame::new(faces), transform))
+    }
+
+    /// Spawns `bundle`, which may contain a [`BoxFrame`], and updates the app.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.app.world_mut().spawn(bundle).id();
+        self.update();
+        entity
+    }
+
+    /// The [`BoxFrame`] component of `entity`.
+    pub fn frame(&self, entity: Entity) -> &BoxFrame {
+        self.app.world().get::<BoxFrame>(entity).unwrap()
+    }
+
+    /// The [`BoxFrame`] component of `entity`, for modification.
+    pub fn frame_mut(&mut self, entity: Entity) -> Mut<'_, BoxFrame> {
+        self.app.world_mut().get_mut::<BoxFrame>(entity).unwrap()
+    }
+
+    /// Runs one update of the app.
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Projects `world_point` onto the virtual window.
+    pub fn world_to_viewport(&mut self, world_point: Vec3) -> Vec2 {
+        let world = self.app.world_mut();
+        let mut cameras = world.query::<(&Camera, &GlobalTransform)>();
+        let (camera, camera_transform) = cameras.get(world, self.camera).unwrap();
+        camera
+            .world_to_viewport(camera_transform, world_point)
+            .unwrap()
+    }
+
+    /// The current position of the pointer on the virtual window.
+    pub fn pointer_position(&self) -> Vec2 {
+        self.pointer_position
+    }
+
+    /// Moves the pointer to `position` on the virtual window and updates the
+    /// app.
+    pub fn move_pointer(&mut self, position: Vec2) {
+        let delta = position - self.pointer_position;
+        self.pointer_position = position;
+        self.send_pointer_input(PointerAction::Move { delta });
+    }
+
+    /// Moves the pointer over `world_point` and updates the app.
+    pub fn hover(&mut self, world_point: Vec3) {
+        let position = self.world_to_viewport(world_point);
+        self.move_pointer(position);
+    }
+
+    /// Replaces the pointer's ray in the [`RayMap`] with `ray`, regardless of
+    /// the pointer's position. `None` restores the ray computed from the
+    /// pointer's position.
+    ///
+    /// Takes effect on the next update.
+    pub fn set_pointer_ray(&mut self, ray: Option<Ray3d>) {
+        self.app.world_mut().resource_mut::<PointerRayOverride>().0 = ray;
+    }
+
+    /// Presses `button` and updates the app.
+    pub fn press(&mut self, button: PointerButton) {
+        self.send_pointer_input(PointerAction::Press(button));
+    }
+
+    /// Releases `button` and updates the app.
+    pub fn release(&mut self, button: PointerButton) {
+        self.send_pointer_input(PointerAction::Release(button));
+    }
+
+    /// Presses and releases `button` over `world_point`.
+    pub fn click(&mut self, world_point: Vec3) {
+        self.hover(world_point);
+        self.press(PointerButton::Primary);
+        self.release(PointerButton::Primary);
+    }
+
+    /// Drags the primary button from `from` to `to`, both in world
+    /// coordinates, in a few small steps.
+    pub fn drag(&mut self, from: Vec3, to: Vec3) {
+        self.hover(from);
+        self.press(PointerButton::Primary);
+        let start = self.pointer_position;
+        let end = self.world_to_viewport(to);
+        for step in 1..=DRAG_STEPS {
+            self.move_pointer(start.lerp(end, step as f32 / DRAG_STEPS as f32));
+        }
+        self.release(PointerButton::Primary);
+    }
+
+    /// The faces of `frame` that are currently highlighted.
+    pub fn highlighted_faces(&self, frame: Entity) -> Vec<FaceIndex> {
+        let frame = self.frame(frame);
+        frame
+            .face_entities()
+            .into_iter()
+            .enumerate()
+            .filter(|&(face, entity)| {
+                self.app
+                    .world()
+                    .get::<PolylineMaterialHandle>(entity)
+                    .is_some_and(|m| m.0 == frame.visuals.highlight_material(face).0)
+            })
+            .map(|(face, _)| face)
+            .collect()
+    }
+
+    /// The faces of `frame` whose handles are currently visible.
+    pub fn visible_handles(&self, frame: Entity) -> Vec<FaceIndex> {
+        self.frame(frame)
+            .handle_entities()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, entity)| {
+                self.app.world().get::<Visibility>(entity) == Some(&Visibility::Visible)
+            })
+            .map(|(face, _)| face)
+            .collect()
+    }
+
+    /// Runs only the picking backend for the rays computed by the last update
+    /// and returns the entities it hit, without updating the rest of the app.
+    ///
+    /// Replaces the pending [`PointerHits`] events, so this is meant for
+    /// measuring picking performance rather than in between interactions.
+    pub fn pick(&mut self) -> Vec<Entity> {
+        let world = self.app.world_mut();
+        world.resource_mut::<Events<PointerHits>>().clear();
+        world.run_system_once(box_frame_backend).unwrap();
+        world
+            .resource_mut::<Events<PointerHits>>()
+            .drain()
+            .flat_map(|hits| hits.picks.into_iter().map(|(entity, _)| entity))
+            .collect()
+    }
+
+    fn send_pointer_input(&mut self, action: PointerAction) {
+        let world = self.app.world_mut();
+        let window = world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(world)
+            .unwrap();
+        let target = NormalizedRenderTarget::Window(
+            bevy::window::WindowRef::Entity(window)
+                .normalize(None)
+                .unwrap(),
+        );
+        world.send_event(PointerInput::new(
+            TEST_POINTER,
+            Location {
+                target,
+                position: self.pointer_position,
+            },
+            action,
+        ));
+        self.update();
+    }
+}
+
+fn override_pointer_ray(
+    ray_override: Res<PointerRayOverride>,
+    cameras: Query<Entity, With<Camera>>,
+    mut ray_map: ResMut<RayMap>,
+) {
+    let Some(ray) = ray_override.0 else {
+        return;
+    };
+    for camera in &cameras {
+        ray_map.map.insert(RayId::new(camera, TEST_POINTER), ray);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    box_frame::BoxFrameHandle, BoxFrame, BoxFrameVisuals, HandleShape, ScalingFn, Selected,
+    SolidColorMaterial,
+};
+use bevy::{
+    color::palettes::css::{BLACK, BLUE, DARK_ORANGE, GOLD, GRAY, LIME, RED, WHITE},
+    prelude::*,
+};
+use bevy_polyline::prelude::PolylineMaterialHandle;
+
+/// The visuals of every [`BoxFrame`] that doesn't specify its own with
+/// [`BoxFrame::with_visuals`].
+///
+/// [`BoxFramePlugin`](crate::BoxFramePlugin) creates this from its
+/// [`style`](crate::BoxFramePlugin::style) unless it already exists. Replacing
+/// or modifying the resource updates the materials, meshes and handle sizes of
+/// those frames, e.g. to switch between dark and light viewports:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fsl_box_frame::{BoxFrameStyle, BoxFrameTheme, BoxFrameVisuals, SolidColorMaterial};
+/// # use bevy_polyline::prelude::PolylineMaterial;
+/// fn use_light_theme(
+///     mut theme: ResMut<BoxFrameTheme>,
+///     mut line_materials: ResMut<Assets<PolylineMaterial>>,
+///     mut meshes: ResMut<Assets<Mesh>>,
+///     mut materials: ResMut<Assets<SolidColorMaterial>>,
+/// ) {
+///     theme.0 = BoxFrameVisuals::from_style(
+///         &BoxFrameStyle::light(),
+///         &mut line_materials,
+///         &mut meshes,
+///         &mut materials,
+///     );
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct BoxFrameTheme(pub BoxFrameVisuals);
+
+/// Colors and sizes that [`BoxFrameVisuals::from_style`] turns into assets.
+#[derive(Clone, Debug)]
+pub struct BoxFrameStyle {
+    /// Color of frame edges.
+    pub edge_color: Color,
+    /// Width of frame edges in pixels.
+    pub edge_width: f32,
+    /// Color of the edges of a [`Selected`] frame.
+    pub selected_edge_color: Color,
+    /// Width of the edges of a [`Selected`] frame in pixels.
+    pub selected_edge_width: f32,
+    /// Color of the edges of a highlighted face.
+    pub highlight_color: Color,
+    /// Width of the edges of a highlighted face in pixels.
+    pub highlight_width: f32,
+    /// Color of face handles.
+    pub handle_color: Color,
+    /// See [`BoxFrameVisuals::handle_shape`].
+    pub handle_shape: HandleShape,
+    /// Colors of the handles and highlighted edges of the faces along the X, Y
+    /// and Z axes, used instead of `handle_color` and `highlight_color`.
+    pub axis_colors: Option<[Color; 3]>,
+    /// See [`BoxFrameVisuals::handle_scale`].
+    pub handle_scale: ScalingFn,
+    /// See [`BoxFrameVisuals::handle_hover_scale`].
+    pub handle_hover_scale: f32,
+}
+
+impl Default for BoxFrameStyle {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl BoxFrameStyle {
+    /// Light edges for dark backgrounds.
+    pub fn dark() -> Self {
+        Self {
+            edge_color: WHITE.into(),
+            edge_width: 1.0,
+            selected_edge_color: GOLD.into(),
+            selected_edge_width: 2.0,
+            highlight_color: WHITE.into(),
+            highlight_width: 3.0,
+            handle_color: RED.into(),
+            handle_shape: HandleShape::Ball,
+            axis_colors: Some([RED.into(), LIME.into(), BLUE.into()]),
+            handle_scale: |e| 0.05 * crate::median3(e),
+            handle_hover_scale: 1.2,
+        }
+    }
+
+    /// Dark edges for light backgrounds.
+    pub fn light() -> Self {
+        Self {
+            edge_color: GRAY.into(),
+            selected_edge_color: DARK_ORANGE.into(),
+            highlight_color: BLACK.into(),
+            ..Self::dark()
+        }
+    }
+}
+
+/// Applies a changed [`BoxFrameTheme`] to the frames that use it.
+pub(crate) fn apply_theme(
+    theme: Res<BoxFrameTheme>,
+    mut box_frames: Query<(&mut BoxFrame, Has<Selected>)>,
+    mut line_materials: Query<&mut PolylineMaterialHandle>,
+    mut handles: Query<(
+        &mut BoxFrameHandle,
+        &mut Transform,
+        &mut Mesh3d,
+        &mut MeshMaterial3d<SolidColorMaterial>,
+    )>,
+) {
+    let visuals = &theme.0;
+    for (mut frame, selected) in &mut box_frames {
+        if !frame.inherit_visuals {
+            continue;
+        }
+
+        // Keep highlighted faces highlighted.
+        let old_visuals = std::mem::replace(&mut frame.visuals, visuals.clone());
+        let edge_material = frame.edge_material(selected).clone();
+        for (face, face_entity) in frame.face_entities().into_iter().enumerate() {
+            let Ok(mut material) = line_materials.get_mut(face_entity) else {
+                continue;
+            };
+            *material = if material.0 == old_visuals.highlight_material(face).0 {
+                visuals.highlight_material(face).clone()
+            } else {
+                edge_material.clone()
+            };
+        }
+
+        let base_scale = (visuals.handle_scale)(frame.extents());
+        for (face, handle_entity) in frame.handle_entities().into_iter().enumerate() {
+            let Ok((mut handle, mut transform, mut mesh, mut material)) =
+                handles.get_mut(handle_entity)
+            else {
+                continue;
+            };
+            handle.base_scale = base_scale;
+            handle.hover_scale = visuals.handle_hover_scale;
+            transform.scale = Vec3::splat(base_scale);
+            mesh.0 = visuals.handle_mesh.clone();
+            material.0 = visuals.handle_material(face).clone();
+        }
+    }
+}
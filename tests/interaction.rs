@@ -0,0 +1,801 @@
+use bevy::{math::DVec3, prelude::*, render::mesh::PrimitiveTopology, time::TimeUpdateStrategy};
+use bevy_fsl_box_frame::{
+    testing::{BoxFrameTestApp, TEST_POINTER},
+    BoxFrame, BoxFrameAnimation, BoxFrameGrid, BoxFrameHover, BoxFrameOrigin, BoxFrameTheme,
+    GridAlignment, HandleShape, HandleVisibility, InteractionMode, PreciseFaces, Selected,
+    ShowHandles, SolidColorMaterial,
+};
+use bevy_polyline::prelude::{Polyline, PolylineHandle, PolylineMaterialHandle};
+use std::time::Duration;
+
+const UNIT_CUBE: [f32; 6] = [-0.5, -0.5, -0.5, 0.5, 0.5, 0.5];
+
+const POS_X: usize = 3;
+const POS_Y: usize = 4;
+const POS_Z: usize = 5;
+
+fn assert_faces_eq(actual: [f32; 6], expected: [f32; 6]) {
+    for (a, e) in actual.into_iter().zip(expected) {
+        assert!((a - e).abs() < 1e-3, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn hovering_a_face_highlights_it_and_shows_handles() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    assert!(test.highlighted_faces(frame).is_empty());
+    assert!(test.visible_handles(frame).is_empty());
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert_eq!(test.highlighted_faces(frame), [POS_Y]);
+    assert_eq!(test.visible_handles(frame), [0, 1, 2, 3, 4, 5]);
+
+    test.hover(Vec3::new(0.5, 0.3, -0.3));
+    assert_eq!(test.highlighted_faces(frame), [POS_X]);
+
+    test.hover(Vec3::new(-0.3, -0.3, 0.5));
+    assert_eq!(test.highlighted_faces(frame), [POS_Z]);
+}
+
+#[test]
+fn moving_off_the_frame_clears_highlights_and_hides_handles() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.hover(Vec3::new(3.0, 0.0, 0.0));
+    assert!(test.highlighted_faces(frame).is_empty());
+    assert!(test.visible_handles(frame).is_empty());
+}
+
+#[test]
+fn dragging_a_face_moves_it_along_its_normal() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+
+    test.drag(Vec3::new(0.5, 0.3, -0.3), Vec3::new(1.2, 0.3, -0.3));
+    assert_faces_eq(test.frame(frame).faces(), [-0.5, -0.5, -0.5, 1.2, 0.5, 0.5]);
+    assert!(test.highlighted_faces(frame).is_empty());
+}
+
+#[test]
+fn handles_are_hidden_while_dragging() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(0.3, 0.8, 0.3));
+    assert!(test.visible_handles(frame).is_empty());
+    assert_eq!(test.highlighted_faces(frame), [POS_Y]);
+    test.release(PointerButton::Primary);
+}
+
+#[test]
+fn dragging_a_face_past_its_opposite_sorts_faces_on_release() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, -1.0, 0.3));
+    assert_faces_eq(
+        test.frame(frame).faces(),
+        [-0.5, -1.0, -0.5, 0.5, -0.5, 0.5],
+    );
+}
+
+#[test]
+fn faces_of_rotated_frames_are_picked_in_model_space() {
+    let mut test = BoxFrameTestApp::new();
+    let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::from_rotation(rotation));
+
+    // The model's +Z face points towards world +X.
+    test.drag(Vec3::new(0.5, 0.3, -0.3), Vec3::new(1.0, 0.3, -0.3));
+    assert_faces_eq(test.frame(frame).faces(), [-0.5, -0.5, -0.5, 0.5, 0.5, 1.0]);
+}
+
+#[test]
+fn locked_faces_are_not_highlighted_or_dragged() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.frame_mut(frame).lock_face(POS_Y);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(test.highlighted_faces(frame).is_empty());
+    assert_eq!(test.visible_handles(frame), [0, 1, 2, 3, 5]);
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+}
+
+#[test]
+fn hover_only_frames_are_highlighted_but_not_dragged() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.frame_mut(frame).interaction = InteractionMode::HoverOnly;
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert_eq!(test.highlighted_faces(frame), [POS_Y]);
+    assert!(test.visible_handles(frame).is_empty());
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+}
+
+#[test]
+fn read_only_frames_ignore_pointers() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.frame_mut(frame).interaction = InteractionMode::ReadOnly;
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(test.highlighted_faces(frame).is_empty());
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+}
+
+#[test]
+fn overridden_pointer_rays_drive_picking() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+
+    test.set_pointer_ray(Some(Ray3d::new(Vec3::new(0.2, 0.2, 3.0), Dir3::NEG_Z)));
+    test.move_pointer(Vec2::new(1.0, 1.0));
+    assert_eq!(test.highlighted_faces(frame), [POS_Z]);
+}
+
+#[test]
+fn removing_a_frame_despawns_its_children() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let children: Vec<Entity> = test.app.world().get::<Children>(frame).unwrap().to_vec();
+    assert_eq!(children.len(), 12);
+
+    test.app.world_mut().entity_mut(frame).remove::<BoxFrame>();
+    test.update();
+    assert!(children
+        .iter()
+        .all(|&child| test.app.world().get_entity(child).is_err()));
+    assert!(test.app.world().get_entity(frame).is_ok());
+}
+
+#[test]
+fn changing_the_theme_updates_frames_that_use_it() {
+    let mut test = BoxFrameTestApp::new();
+    let themed = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let custom_visuals = test.default_visuals();
+    let custom = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_visuals(custom_visuals.clone()),
+        Transform::from_xyz(-2.0, 0.0, 0.0),
+    ));
+
+    let new_theme = test.default_visuals();
+    test.app
+        .world_mut()
+        .insert_resource(BoxFrameTheme(new_theme.clone()));
+    test.update();
+
+    let edge_material = |test: &BoxFrameTestApp, frame: Entity| {
+        let face = test.app.world().get::<Children>(frame).unwrap()[0];
+        test.app
+            .world()
+            .get::<PolylineMaterialHandle>(face)
+            .unwrap()
+            .0
+            .clone()
+    };
+    assert_eq!(
+        test.frame(themed).visuals.edge_material.0,
+        new_theme.edge_material.0
+    );
+    assert_eq!(edge_material(&test, themed), new_theme.edge_material.0);
+    assert_eq!(edge_material(&test, custom), custom_visuals.edge_material.0);
+
+    // Highlights use the new theme too.
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert_eq!(test.highlighted_faces(themed), [POS_Y]);
+}
+
+#[test]
+fn faces_use_per_axis_materials() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let visuals = test.frame(frame).visuals.clone();
+    let highlights = visuals.face_highlight_materials.clone().unwrap();
+    let handles = visuals.face_handle_materials.clone().unwrap();
+    assert_ne!(highlights[POS_X].0, highlights[POS_Y].0);
+    assert_eq!(highlights[POS_X].0, highlights[0].0);
+
+    let children = test.app.world().get::<Children>(frame).unwrap().to_vec();
+    let (faces, handle_entities) = children.split_at(6);
+    let material = |test: &BoxFrameTestApp, entity: Entity| {
+        test.app
+            .world()
+            .get::<PolylineMaterialHandle>(entity)
+            .unwrap()
+            .0
+            .clone()
+    };
+    for (face, &handle) in handle_entities.iter().enumerate() {
+        let handle_material = test
+            .app
+            .world()
+            .get::<MeshMaterial3d<SolidColorMaterial>>(handle)
+            .unwrap();
+        assert_eq!(handle_material.0, handles[face]);
+    }
+
+    test.hover(Vec3::new(0.5, 0.3, -0.3));
+    assert_eq!(material(&test, faces[POS_X]), highlights[POS_X].0);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert_eq!(material(&test, faces[POS_Y]), highlights[POS_Y].0);
+    assert_eq!(material(&test, faces[POS_X]), visuals.edge_material.0);
+}
+
+#[test]
+fn handles_are_picked_against_their_shape() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let handle = test.app.world().get::<Children>(frame).unwrap()[6 + POS_Y];
+    let handle_scale =
+        |test: &BoxFrameTestApp| test.app.world().get::<Transform>(handle).unwrap().scale.x;
+    let base_scale = handle_scale(&test);
+
+    // This ray passes near the corner of a cube handle, outside of a ball
+    // handle.
+    let corner = 0.9 * base_scale;
+    let ray = Ray3d::new(Vec3::new(corner, 2.0, corner), Dir3::NEG_Y);
+    test.set_pointer_ray(Some(ray));
+    // The pointer needs a location to generate hits.
+    test.move_pointer(Vec2::ZERO);
+    assert_eq!(handle_scale(&test), base_scale);
+
+    let mut visuals = test.frame(frame).visuals.clone();
+    visuals.handle_shape = HandleShape::Cube;
+    test.frame_mut(frame).visuals = visuals;
+    test.update();
+    assert!(handle_scale(&test) > base_scale);
+}
+
+#[test]
+fn handles_point_along_face_normals() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let children = test.app.world().get::<Children>(frame).unwrap().to_vec();
+    for (face, normal) in [
+        Vec3::NEG_X,
+        Vec3::NEG_Y,
+        Vec3::NEG_Z,
+        Vec3::X,
+        Vec3::Y,
+        Vec3::Z,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let rotation = test
+            .app
+            .world()
+            .get::<Transform>(children[6 + face])
+            .unwrap()
+            .rotation;
+        assert!((rotation * Vec3::Y).distance(normal) < 1e-5);
+    }
+}
+
+#[test]
+fn handle_visibility_policies() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_handle_visibility(HandleVisibility {
+            show: ShowHandles::Always,
+            front_faces_only: true,
+            keep_dragged_handle: true,
+            ..default()
+        }),
+        Transform::default(),
+    ));
+    // The camera only sees the positive faces.
+    assert_eq!(test.visible_handles(frame), [POS_X, POS_Y, POS_Z]);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(0.3, 0.8, 0.3));
+    assert_eq!(test.visible_handles(frame), [POS_Y]);
+    test.release(PointerButton::Primary);
+
+    test.frame_mut(frame).handle_visibility.show = ShowHandles::WhenSelected;
+    test.update();
+    assert!(test.visible_handles(frame).is_empty());
+    test.app.world_mut().entity_mut(frame).insert(Selected);
+    test.update();
+    assert_eq!(test.visible_handles(frame), [POS_X, POS_Y, POS_Z]);
+}
+
+#[test]
+fn handles_fade_in_and_out() {
+    let mut test = BoxFrameTestApp::new();
+    test.app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+    let frame = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_handle_visibility(HandleVisibility {
+            fade_seconds: 0.25,
+            ..default()
+        }),
+        Transform::default(),
+    ));
+    let handle = test.app.world().get::<Children>(frame).unwrap()[6 + POS_Y];
+    let base_material = test.frame(frame).visuals.handle_material(POS_Y).clone();
+    let alpha = |test: &BoxFrameTestApp| {
+        let material = test
+            .app
+            .world()
+            .get::<MeshMaterial3d<SolidColorMaterial>>(handle)
+            .unwrap();
+        let materials = test.app.world().resource::<Assets<SolidColorMaterial>>();
+        materials.get(&material.0).unwrap().color.alpha
+    };
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(test.visible_handles(frame).contains(&POS_Y));
+    assert!(alpha(&test) < 1.0);
+    for _ in 0..3 {
+        test.update();
+    }
+    assert_eq!(alpha(&test), 1.0);
+    assert_eq!(
+        test.app
+            .world()
+            .get::<MeshMaterial3d<SolidColorMaterial>>(handle)
+            .unwrap()
+            .0,
+        base_material
+    );
+
+    test.hover(Vec3::new(3.0, 0.0, 0.0));
+    assert!(test.visible_handles(frame).contains(&POS_Y));
+    for _ in 0..3 {
+        test.update();
+    }
+    assert!(test.visible_handles(frame).is_empty());
+}
+
+#[test]
+fn edges_of_thin_frames_pick_the_face_seen_edge_on() {
+    const SLAB: [f32; 6] = [-1.0, -0.1, -1.0, 1.0, 0.1, 1.0];
+    let mut test = BoxFrameTestApp::new();
+    test.set_camera_transform(Transform::from_xyz(0.0, 0.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y));
+    let frame = test.spawn_frame(SLAB, Transform::default());
+
+    // Just above the top edge of the +Z face, outside of the box.
+    let near_edge = Vec3::new(0.3, 0.115, 1.0);
+    test.frame_mut(frame).edge_pick_tolerance = 0.0;
+    test.hover(near_edge);
+    assert!(test.highlighted_faces(frame).is_empty());
+
+    test.frame_mut(frame).edge_pick_tolerance = 5.0;
+    test.hover(near_edge);
+    assert_eq!(test.highlighted_faces(frame), [POS_Y]);
+
+    test.drag(near_edge, Vec3::new(0.3, 0.5, 1.0));
+    let faces = test.frame(frame).faces();
+    assert!((faces[POS_Y] - 0.5).abs() < 0.05, "{faces:?}");
+}
+
+#[test]
+fn small_distant_frames_pick_the_face_under_the_pointer() {
+    let mut test = BoxFrameTestApp::new();
+    // From high above, the frame is a few pixels wide and its sides are seen
+    // almost edge-on, so every point on the top face is near an edge.
+    test.set_camera_transform(
+        Transform::from_xyz(20.0, 100.0, 30.0).looking_at(Vec3::ZERO, Vec3::Y),
+    );
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    // Handles would take priority over the faces.
+    test.frame_mut(frame).interaction = InteractionMode::HoverOnly;
+
+    test.hover(Vec3::new(0.0, 0.5, 0.0));
+    assert_eq!(test.highlighted_faces(frame), [POS_Y]);
+}
+
+#[test]
+fn cameras_inside_a_frame_pick_the_faces_they_look_at() {
+    const ROOM: [f32; 6] = [-5.0, -5.0, -5.0, 5.0, 5.0, 5.0];
+    let mut test = BoxFrameTestApp::new();
+    test.set_camera_transform(Transform::default().looking_at(Vec3::new(5.0, 0.0, -5.0), Vec3::Y));
+    let frame = test.spawn_frame(ROOM, Transform::default());
+
+    test.hover(Vec3::new(5.0, 0.5, -2.0));
+    assert_eq!(test.highlighted_faces(frame), [POS_X]);
+    test.hover(Vec3::new(2.0, 0.5, -5.0));
+    assert_eq!(test.highlighted_faces(frame), [2]);
+
+    test.drag(Vec3::new(5.0, 0.5, -2.0), Vec3::new(4.0, 0.5, -2.0));
+    let faces = test.frame(frame).faces();
+    assert!((faces[POS_X] - 4.0).abs() < 1e-2, "{faces:?}");
+}
+
+#[test]
+fn cameras_inside_a_rotated_frame_pick_the_faces_they_look_at() {
+    const ROOM: [f32; 6] = [-5.0, -5.0, -5.0, 5.0, 5.0, 5.0];
+    let mut test = BoxFrameTestApp::new();
+    test.set_camera_transform(
+        Transform::from_xyz(1.0, 0.0, 1.0).looking_at(Vec3::new(5.0, 0.0, -5.0), Vec3::Y),
+    );
+    let frame = test.spawn_frame(
+        ROOM,
+        Transform::from_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),
+    );
+
+    // Local +Z is world +X.
+    test.hover(Vec3::new(5.0, 0.5, -2.0));
+    assert_eq!(test.highlighted_faces(frame), [POS_Z]);
+
+    test.drag(Vec3::new(5.0, 0.5, -2.0), Vec3::new(4.0, 0.5, -2.0));
+    let faces = test.frame(frame).faces();
+    assert!((faces[POS_Z] - 4.0).abs() < 1e-2, "{faces:?}");
+}
+
+#[test]
+fn hover_state_is_exposed() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let hover = |test: &BoxFrameTestApp| *test.app.world().get::<BoxFrameHover>(frame).unwrap();
+    assert_eq!(hover(&test).face(), None);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.2));
+    let hovered = hover(&test).0.unwrap();
+    assert_eq!(hovered.face, POS_Y);
+    assert_eq!(hovered.handle, None);
+    assert_eq!(hovered.pointer, TEST_POINTER);
+    assert!(hovered.position.distance(Vec3::new(0.3, 0.5, 0.2)) < 1e-3);
+
+    test.hover(Vec3::new(0.0, 0.5, 0.0));
+    let hovered = hover(&test).0.unwrap();
+    assert_eq!(hovered.face, POS_Y);
+    let handle = test.app.world().get::<Children>(frame).unwrap()[6 + POS_Y];
+    assert_eq!(hovered.handle, Some(handle));
+
+    test.hover(Vec3::new(3.0, 0.0, 0.0));
+    assert_eq!(hover(&test).face(), None);
+}
+
+#[cfg(feature = "cursor")]
+#[test]
+fn cursor_icons_show_the_drag_direction() {
+    use bevy::window::{PrimaryWindow, SystemCursorIcon};
+    use bevy::winit::cursor::CursorIcon;
+    use bevy_fsl_box_frame::BoxFrameCursorPlugin;
+
+    let mut test = BoxFrameTestApp::with_plugins(BoxFrameCursorPlugin);
+    test.spawn_frame(UNIT_CUBE, Transform::default());
+    let window = test
+        .app
+        .world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(test.app.world())
+        .unwrap();
+    let cursor = |test: &BoxFrameTestApp| test.app.world().get::<CursorIcon>(window).cloned();
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert_eq!(
+        cursor(&test),
+        Some(CursorIcon::System(SystemCursorIcon::NsResize))
+    );
+
+    test.hover(Vec3::new(3.0, 0.0, 0.0));
+    assert_eq!(cursor(&test), None);
+}
+
+#[test]
+fn dragging_modifies_polylines_in_place() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let line_handles = |test: &BoxFrameTestApp| {
+        let children = test.app.world().get::<Children>(frame).unwrap();
+        children[..6]
+            .iter()
+            .map(|&face| test.app.world().get::<PolylineHandle>(face).unwrap().0.id())
+            .collect::<Vec<_>>()
+    };
+    let before = line_handles(&test);
+    let line_count = test.app.world().resource::<Assets<Polyline>>().len();
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert_eq!(line_handles(&test), before);
+    assert_eq!(
+        test.app.world().resource::<Assets<Polyline>>().len(),
+        line_count
+    );
+
+    let top = test
+        .app
+        .world()
+        .resource::<Assets<Polyline>>()
+        .get(before[POS_Y])
+        .unwrap();
+    assert!(top.vertices.iter().all(|v| (v.y - 1.0).abs() < 1e-3));
+}
+
+#[test]
+fn precise_faces_are_dragged_relative_to_the_origin() {
+    let mut test = BoxFrameTestApp::new();
+    test.app
+        .world_mut()
+        .insert_resource(BoxFrameOrigin(DVec3::new(500_000.0, 0.0, 6_000_000.0)));
+    let faces = [
+        500_000.25,
+        -0.5,
+        6_000_000.125,
+        500_001.25,
+        0.5,
+        6_000_001.125,
+    ];
+    let frame = test.spawn((BoxFrame::new([0.0; 6]), PreciseFaces::new(faces)));
+    test.update();
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+    let translation = test
+        .app
+        .world()
+        .get::<Transform>(frame)
+        .unwrap()
+        .translation;
+    assert!(translation.abs_diff_eq(Vec3::new(0.75, 0.0, 0.625), 1e-6));
+
+    // Only the dragged face moves, and the other faces stay exact.
+    test.drag(
+        translation + Vec3::new(0.3, 0.5, 0.3),
+        translation + Vec3::new(0.3, 1.0, 0.3),
+    );
+    test.update();
+    let precise = *test.app.world().get::<PreciseFaces>(frame).unwrap();
+    assert!((precise.faces()[POS_Y] - 1.0).abs() < 1e-3);
+    for face in [0, 1, 2, 3, 5] {
+        assert_eq!(precise.faces()[face], faces[face]);
+    }
+    assert_faces_eq(
+        test.frame(frame).faces(),
+        [-0.5, -0.75, -0.5, 0.5, 0.75, 0.5],
+    );
+
+    // Moving the origin moves the frame in world coordinates.
+    test.app.world_mut().resource_mut::<BoxFrameOrigin>().0.x += 1.0;
+    test.update();
+    let moved = test
+        .app
+        .world()
+        .get::<Transform>(frame)
+        .unwrap()
+        .translation;
+    assert!(moved.abs_diff_eq(Vec3::new(-0.25, 0.25, 0.625), 1e-3));
+
+    // Faces can be written in high precision.
+    test.app
+        .world_mut()
+        .get_mut::<PreciseFaces>(frame)
+        .unwrap()
+        .set_faces(faces);
+    test.update();
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+}
+
+#[test]
+fn section_planes_are_dragged_between_their_faces() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_slice(1, 0.0),
+        Transform::default(),
+    ));
+    test.update();
+
+    // The plane's outline takes priority over the face under it.
+    test.hover(Vec3::new(0.5, 0.0, -0.2));
+    assert!(test.highlighted_faces(frame).is_empty());
+
+    test.drag(Vec3::new(0.5, 0.0, -0.2), Vec3::new(0.5, 0.3, -0.2));
+    let slice = test.frame(frame).slices[1].unwrap();
+    assert!((slice - 0.3).abs() < 1e-3, "{slice}");
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+
+    // Planes can't leave the box.
+    test.drag(Vec3::new(0.5, 0.3, -0.2), Vec3::new(0.5, 1.5, -0.2));
+    assert_eq!(test.frame(frame).slices[1], Some(0.5));
+    assert_faces_eq(test.frame(frame).faces(), UNIT_CUBE);
+
+    // Without the plane, the face is picked again.
+    test.frame_mut(frame).slices[1] = None;
+    test.update();
+    test.hover(Vec3::new(0.5, 0.0, -0.2));
+    assert_eq!(test.highlighted_faces(frame), [POS_X]);
+}
+
+#[test]
+fn moved_faces_are_animated() {
+    let mut test = BoxFrameTestApp::new();
+    test.app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+    let still = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.0, 0.0));
+    let animated = test.spawn((
+        BoxFrame::new(UNIT_CUBE),
+        BoxFrameAnimation {
+            seconds: 0.4,
+            ease: EaseFunction::Linear,
+            ..default()
+        },
+        Transform::default(),
+    ));
+    let moved = [-0.5, -0.5, -0.5, 1.5, 0.5, 0.5];
+    test.frame_mut(still).move_faces(moved);
+    test.frame_mut(animated).move_faces(moved);
+    test.update();
+    assert_faces_eq(test.frame(still).faces(), moved);
+    let x = test.frame(animated).faces()[POS_X];
+    assert!(x > 0.5 && x < 1.5, "{x}");
+    assert!(test.frame(animated).is_animating());
+
+    for _ in 0..4 {
+        test.update();
+    }
+    assert_faces_eq(test.frame(animated).faces(), moved);
+    assert!(!test.frame(animated).is_animating());
+
+    // Handles follow the animated faces.
+    test.hover(Vec3::new(1.5, 0.3, -0.3));
+    assert_eq!(test.highlighted_faces(animated), [POS_X]);
+}
+
+#[derive(Resource, Default)]
+struct ChangedFrames(usize);
+
+fn count_changed_frames(frames: Query<(), Changed<BoxFrame>>, mut changed: ResMut<ChangedFrames>) {
+    changed.0 += frames.iter().count();
+}
+
+#[test]
+fn idle_frames_are_not_changed() {
+    let mut test = BoxFrameTestApp::new();
+    test.app
+        .init_resource::<ChangedFrames>()
+        .add_systems(Last, count_changed_frames);
+    test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.spawn((
+        BoxFrame::new(UNIT_CUBE),
+        BoxFrameAnimation::default(),
+        Transform::from_xyz(-2.0, 0.0, 0.0),
+    ));
+    test.update();
+
+    test.app.world_mut().resource_mut::<ChangedFrames>().0 = 0;
+    test.update();
+    assert_eq!(test.app.world().resource::<ChangedFrames>().0, 0);
+}
+
+#[test]
+fn smoothed_drags_lag_behind_the_pointer_and_settle_on_release() {
+    let mut test = BoxFrameTestApp::new();
+    test.app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+    let frame = test.spawn((
+        BoxFrame::new(UNIT_CUBE),
+        BoxFrameAnimation {
+            drag_smoothing_seconds: 0.2,
+            ..default()
+        },
+        Transform::default(),
+    ));
+
+    test.hover(Vec3::new(0.5, 0.3, -0.3));
+    test.press(PointerButton::Primary);
+    let end = test.world_to_viewport(Vec3::new(1.2, 0.3, -0.3));
+    test.move_pointer(end);
+    let x = test.frame(frame).faces()[POS_X];
+    assert!(x > 0.5 && x < 1.1, "{x}");
+
+    test.release(PointerButton::Primary);
+    for _ in 0..5 {
+        test.update();
+    }
+    assert_faces_eq(test.frame(frame).faces(), [-0.5, -0.5, -0.5, 1.2, 0.5, 0.5]);
+}
+
+/// The number of grid and tick vertices drawn for `frame`.
+fn grid_vertex_count(test: &mut BoxFrameTestApp, frame: Entity) -> usize {
+    let world = test.app.world_mut();
+    let mut lines = world.query::<(&ChildOf, &Mesh3d, &Visibility)>();
+    let meshes = world.resource::<Assets<Mesh>>();
+    lines
+        .iter(world)
+        .filter(|(parent, ..)| parent.parent() == frame)
+        .filter_map(|(_, mesh, visibility)| {
+            let mesh = meshes.get(&mesh.0)?;
+            (mesh.primitive_topology() == PrimitiveTopology::LineList)
+                .then(|| mesh.count_vertices())
+                .filter(|_| visibility != Visibility::Hidden)
+        })
+        .sum()
+}
+
+#[test]
+fn grids_follow_faces_and_thin_out_with_distance() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn((BoxFrame::new(UNIT_CUBE), BoxFrameGrid::new(0.25)));
+    test.update();
+    // 3 lines along each of the 2 axes of each face.
+    assert_eq!(grid_vertex_count(&mut test, frame), 2 * 6 * 2 * 3);
+
+    // Lines are regenerated when faces move.
+    test.drag(Vec3::new(0.5, 0.3, -0.3), Vec3::new(0.75, 0.3, -0.3));
+    test.update();
+    assert_eq!(grid_vertex_count(&mut test, frame), 2 * (6 * 2 * 3 + 4));
+
+    // World-aligned lines are anchored at the origin instead of the faces.
+    test.app
+        .world_mut()
+        .entity_mut(frame)
+        .insert(BoxFrameGrid::new(0.25).with_alignment(GridAlignment::World));
+    test.update();
+    // Along X, the box spans (-0.5, 0.75), so there are lines at -0.25, 0,
+    // 0.25 and 0.5.
+    assert_eq!(grid_vertex_count(&mut test, frame), 2 * (6 * 2 * 3 + 4));
+
+    // Ticks on both faces of each edge.
+    test.app
+        .world_mut()
+        .entity_mut(frame)
+        .insert(BoxFrameGrid::new(0.0).with_ticks(0.25, 0.05));
+    test.update();
+    assert_eq!(grid_vertex_count(&mut test, frame), 2 * 2 * (8 * 3 + 4 * 4));
+
+    // Far away, ticks are doubled until they are far enough apart on screen.
+    test.set_camera_transform(
+        Transform::from_xyz(60.0, 80.0, 100.0).looking_at(Vec3::ZERO, Vec3::Y),
+    );
+    test.update();
+    let far = grid_vertex_count(&mut test, frame);
+    assert!(far < 2 * 2 * (8 * 3 + 4 * 4), "{far}");
+
+    test.app
+        .world_mut()
+        .entity_mut(frame)
+        .remove::<BoxFrameGrid>();
+    test.update();
+    assert_eq!(grid_vertex_count(&mut test, frame), 0);
+}
+
+#[test]
+fn removing_a_frame_despawns_its_grid_lines() {
+    let mut test = BoxFrameTestApp::new();
+    let frame = test.spawn((BoxFrame::new(UNIT_CUBE), BoxFrameGrid::new(0.25)));
+    test.update();
+    let world = test.app.world_mut();
+    let (lines, mesh) = world
+        .query::<(Entity, &ChildOf, &Mesh3d)>()
+        .iter(world)
+        .find(|(_, parent, _)| parent.parent() == frame)
+        .map(|(entity, _, mesh)| (entity, mesh.0.id()))
+        .unwrap();
+
+    test.app.world_mut().entity_mut(frame).remove::<BoxFrame>();
+    test.update();
+    test.update();
+    assert!(test.app.world().get_entity(lines).is_err());
+    assert!(!test.app.world().resource::<Assets<Mesh>>().contains(mesh));
+
+    // Lines are drawn again when the frame comes back.
+    test.app
+        .world_mut()
+        .entity_mut(frame)
+        .insert(BoxFrame::new(UNIT_CUBE));
+    test.update();
+    test.update();
+    assert_eq!(grid_vertex_count(&mut test, frame), 2 * 6 * 2 * 3);
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "interchange")]
+
+use bevy::{math::DVec3, prelude::*};
+use bevy_fsl_box_frame::{
+    interchange::{self, Axes, BoxFrameCorners, DxfEntity},
+    BoxFrame, BoxFrameOrigin,
+};
+use std::io::Cursor;
+
+fn boxes() -> Vec<BoxFrameCorners> {
+    let origin = BoxFrameOrigin(DVec3::new(500_000.0, 0.0, 6_000_000.0));
+    let rotated = Transform::from_xyz(1.0, 2.0, 3.0)
+        .with_rotation(Quat::from_euler(EulerRot::YXZ, 0.4, 0.2, -0.1))
+        .with_scale(Vec3::new(2.0, 1.0, 1.0));
+    vec![
+        BoxFrameCorners::new(
+            &BoxFrame::new([-0.5, -0.5, -0.5, 0.5, 0.5, 0.5]),
+            &GlobalTransform::IDENTITY,
+            &BoxFrameOrigin::default(),
+        ),
+        BoxFrameCorners::new(
+            &BoxFrame::new([0.0, -1.0, 0.5, 3.0, 2.0, 1.0]),
+            &rotated.into(),
+            &origin,
+        ),
+    ]
+}
+
+fn assert_same_boxes(actual: &[BoxFrameCorners], expected: &[BoxFrameCorners]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        // Imported boxes may have permuted axes, so compare unordered corners.
+        for corner in e.0 {
+            assert!(
+                a.0.iter().any(|c| c.distance(corner) < 1e-3),
+                "{corner} not in {a:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn text_formats_round_trip() {
+    let boxes = boxes();
+    for axes in [Axes::YUp, Axes::ZUp] {
+        let mut obj = Vec::new();
+        interchange::write_obj(&boxes, axes, &mut obj).unwrap();
+        assert_same_boxes(&interchange::read_obj(&obj[..], axes).unwrap(), &boxes);
+
+        let mut stl = Vec::new();
+        interchange::write_stl_ascii(&boxes, axes, &mut stl).unwrap();
+        assert_same_boxes(&interchange::read_stl(&stl[..], axes).unwrap(), &boxes);
+
+        for entity in [DxfEntity::Face, DxfEntity::Polyline] {
+            let mut dxf = Vec::new();
+            interchange::write_dxf(&boxes, axes, entity, &mut dxf).unwrap();
+            assert_same_boxes(&interchange::read_dxf(&dxf[..], axes).unwrap(), &boxes);
+        }
+
+        let mut geojson = Vec::new();
+        interchange::write_geojson(&boxes, axes, &mut geojson).unwrap();
+        assert_same_boxes(
+            &interchange::read_geojson(&geojson[..], axes).unwrap(),
+            &boxes,
+        );
+
+        let mut csv = Vec::new();
+        interchange::write_csv(&boxes, axes, &mut csv).unwrap();
+        assert_same_boxes(
+            &interchange::read_csv(Cursor::new(csv), axes).unwrap(),
+            &boxes,
+        );
+    }
+}
+
+#[test]
+fn binary_stl_round_trips_in_f32() {
+    // Binary STL stores f32 coordinates, so use boxes near the origin.
+    let boxes = &boxes()[..1];
+    let mut stl = Vec::new();
+    interchange::write_stl_binary(boxes, Axes::ZUp, &mut stl).unwrap();
+    assert_eq!(stl.len(), 84 + 50 * 12);
+    assert_same_boxes(&interchange::read_stl(&stl[..], Axes::ZUp).unwrap(), boxes);
+}
+
+#[test]
+fn faces_are_wound_outwards() {
+    let mut stl = Vec::new();
+    interchange::write_stl_ascii(&boxes()[..1], Axes::YUp, &mut stl).unwrap();
+    let stl = String::from_utf8(stl).unwrap();
+    let normals: Vec<Vec3> = stl
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("facet normal "))
+        .map(|normal| {
+            let coords: Vec<f32> = normal.split(' ').map(|c| c.parse().unwrap()).collect();
+            Vec3::new(coords[0], coords[1], coords[2])
+        })
+        .collect();
+    assert_eq!(normals.len(), 12);
+    for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+        for normal in [axis, -axis] {
+            assert_eq!(normals.iter().filter(|&&n| n == normal).count(), 2);
+        }
+    }
+}
+
+#[test]
+fn imported_corners_become_frames() {
+    let origin = BoxFrameOrigin(DVec3::new(1000.0, 0.0, 0.0));
+    let transform = Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(Quat::from_rotation_y(0.3));
+    let corners = BoxFrameCorners::new(
+        &BoxFrame::new([-1.0, -0.5, -2.0, 1.0, 0.5, 2.0]),
+        &transform.into(),
+        &origin,
+    );
+
+    // Shuffled corners are fitted with the rotation closest to the identity.
+    let mut shuffled = corners.0;
+    shuffled.reverse();
+    let fitted = BoxFrameCorners::from_points(&shuffled).unwrap();
+    let (frame, fitted_transform) = fitted.to_frame(&origin);
+    let expected = [-1.0, -0.5, -2.0, 1.0, 0.5, 2.0];
+    for (face, expected) in frame.faces().into_iter().zip(expected) {
+        assert!((face - expected).abs() < 1e-5, "{:?}", frame.faces());
+    }
+    assert!(fitted_transform
+        .translation
+        .abs_diff_eq(transform.translation, 1e-5));
+    assert!(fitted_transform
+        .rotation
+        .abs_diff_eq(transform.rotation, 1e-5));
+
+    // Points that aren't a box are rejected.
+    shuffled[0] += DVec3::X;
+    assert!(BoxFrameCorners::from_points(&shuffled).is_none());
+    assert!(BoxFrameCorners::from_points(&shuffled[..7]).is_none());
+    assert!(interchange::read_csv(&b"frame,corner,x,y,z\n0,0,1,2\n"[..], Axes::YUp).is_err());
+}
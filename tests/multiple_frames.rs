@@ -0,0 +1,713 @@
+use bevy::{prelude::*, render::primitives::Aabb};
+use bevy_fsl_box_frame::{
+    testing::BoxFrameTestApp, BoxFrame, BoxFrameArray, BoxFrameBatch, BoxFrameBlocked,
+    BoxFrameCollisionGroup, BoxFrameCommandsExt, BoxFrameDivided, BoxFrameGroup, BoxFrameSelection,
+    BoxFrameSelectionPlugin, BoxFrameSnapping, BoxFrameSplit, BoxFramesMerged, GroupDragMode,
+    Selected, SnapTarget,
+};
+use bevy_polyline::prelude::PolylineHandle;
+
+const UNIT_CUBE: [f32; 6] = [-0.5, -0.5, -0.5, 0.5, 0.5, 0.5];
+
+const NEG_X: usize = 0;
+const POS_X: usize = 3;
+const POS_Y: usize = 4;
+
+fn selected(test: &BoxFrameTestApp, frame: Entity) -> bool {
+    test.app.world().get::<Selected>(frame).is_some()
+}
+
+#[test]
+fn clicking_selects_frames() {
+    let mut test = BoxFrameTestApp::with_plugins(BoxFrameSelectionPlugin);
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.0, 0.0));
+
+    // Unselected frames aren't editable.
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(test.highlighted_faces(a).is_empty());
+    assert!(test.visible_handles(a).is_empty());
+
+    test.click(Vec3::new(0.3, 0.5, 0.3));
+    assert!(selected(&test, a));
+    test.hover(Vec3::new(0.3, 0.5, 0.2));
+    assert_eq!(test.highlighted_faces(a), [POS_Y]);
+
+    // Clicking another frame replaces the selection.
+    test.click(Vec3::new(-1.7, 0.5, 0.3));
+    assert!(!selected(&test, a));
+    assert!(selected(&test, b));
+
+    // Clicking nothing clears the selection.
+    test.click(Vec3::new(0.0, 3.0, 0.0));
+    assert!(!selected(&test, b));
+}
+
+#[test]
+fn dragging_a_selected_frame_keeps_the_selection() {
+    let mut test = BoxFrameTestApp::with_plugins(BoxFrameSelectionPlugin);
+    test.app
+        .world_mut()
+        .resource_mut::<BoxFrameSelection>()
+        .group_edit = Some(GroupDragMode::Delta);
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.0, 0.0));
+    test.app.world_mut().entity_mut(a).insert(Selected);
+    test.app.world_mut().entity_mut(b).insert(Selected);
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert!((test.frame(b).faces()[POS_Y] - 1.0).abs() < 1e-3);
+    assert!(selected(&test, a));
+    assert!(selected(&test, b));
+
+    // A click without a drag still replaces the selection.
+    test.click(Vec3::new(0.3, 1.0, 0.3));
+    assert!(selected(&test, a));
+    assert!(!selected(&test, b));
+}
+
+#[test]
+fn grouped_frames_follow_dragged_faces() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.0, 0.0));
+    let c = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(0.0, 0.0, -2.0));
+    test.app
+        .world_mut()
+        .entity_mut(a)
+        .insert(BoxFrameGroup::new(1));
+    test.app
+        .world_mut()
+        .entity_mut(b)
+        .insert(BoxFrameGroup::new(1));
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.0).abs() < 1e-3);
+    assert!((test.frame(b).faces()[POS_Y] - 1.0).abs() < 1e-3);
+    assert_eq!(test.frame(c).faces()[POS_Y], 0.5);
+}
+
+#[test]
+fn grouped_frames_can_follow_to_the_same_coordinate() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.5, 0.0));
+    let group = BoxFrameGroup {
+        id: 1,
+        mode: GroupDragMode::Coordinate,
+    };
+    test.app.world_mut().entity_mut(a).insert(group);
+    test.app.world_mut().entity_mut(b).insert(group);
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.5, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.5).abs() < 1e-3);
+    // Frame B is offset upwards by 0.5.
+    assert!((test.frame(b).faces()[POS_Y] - 1.0).abs() < 1e-3);
+}
+
+fn spawn_grouped_pair(test: &mut BoxFrameTestApp) -> (Entity, Entity) {
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.0, 0.0));
+    for frame in [a, b] {
+        test.app
+            .world_mut()
+            .entity_mut(frame)
+            .insert(BoxFrameGroup::new(1));
+    }
+    (a, b)
+}
+
+#[test]
+fn followers_stop_when_their_leader_is_locked() {
+    let mut test = BoxFrameTestApp::new();
+    let (a, b) = spawn_grouped_pair(&mut test);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(0.3, 0.8, 0.3));
+    assert!((test.frame(b).faces()[POS_Y] - 0.8).abs() < 1e-3);
+    test.frame_mut(a).lock_face(POS_Y);
+    test.update();
+    test.release(PointerButton::Primary);
+
+    test.hover(Vec3::new(0.3, 1.2, 0.3));
+    assert!((test.frame(b).faces()[POS_Y] - 0.8).abs() < 1e-3);
+    assert!(test.highlighted_faces(b).is_empty());
+}
+
+#[test]
+fn followers_stop_when_their_leader_is_despawned() {
+    let mut test = BoxFrameTestApp::new();
+    let (a, b) = spawn_grouped_pair(&mut test);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(0.3, 0.8, 0.3));
+    test.app.world_mut().despawn(a);
+    test.update();
+    test.release(PointerButton::Primary);
+
+    test.hover(Vec3::new(0.3, 1.2, 0.3));
+    assert!((test.frame(b).faces()[POS_Y] - 0.8).abs() < 1e-3);
+    assert!(test.highlighted_faces(b).is_empty());
+}
+
+#[test]
+fn followers_of_blocked_frames_are_blocked_too() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.0, 0.0));
+    let blocker = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(2.0, 0.0, 0.0));
+    test.app
+        .world_mut()
+        .entity_mut(a)
+        .insert((BoxFrameGroup::new(1), BoxFrameCollisionGroup(1)));
+    test.app
+        .world_mut()
+        .entity_mut(b)
+        .insert(BoxFrameGroup::new(1));
+    test.app
+        .world_mut()
+        .entity_mut(blocker)
+        .insert(BoxFrameCollisionGroup(1));
+
+    test.drag(Vec3::new(0.5, 0.3, -0.3), Vec3::new(2.0, 0.3, -0.3));
+    assert!((test.frame(a).faces()[POS_X] - 1.5).abs() < 1e-3);
+    assert!((test.frame(b).faces()[POS_X] - 1.5).abs() < 1e-3);
+}
+
+#[test]
+fn frames_in_a_collision_group_block_each_other() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(2.0, 0.0, 0.0));
+    test.app
+        .world_mut()
+        .entity_mut(a)
+        .insert(BoxFrameCollisionGroup(1));
+    test.app
+        .world_mut()
+        .entity_mut(b)
+        .insert(BoxFrameCollisionGroup(1));
+
+    test.hover(Vec3::new(0.5, 0.3, -0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(2.0, 0.3, -0.3));
+    assert!((test.frame(a).faces()[POS_X] - 1.5).abs() < 1e-3);
+    let blocked: Vec<_> = test
+        .app
+        .world_mut()
+        .resource_mut::<Events<BoxFrameBlocked>>()
+        .drain()
+        .collect();
+    assert!(blocked.contains(&BoxFrameBlocked {
+        frame: a,
+        face: POS_X,
+        blocker: b,
+    }));
+    test.release(PointerButton::Primary);
+}
+
+#[test]
+fn dragged_faces_snap_to_other_frames() {
+    let mut test = BoxFrameTestApp::new();
+    test.app.insert_resource(BoxFrameSnapping {
+        ground_plane: None,
+        ..default()
+    });
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let _b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.5, 0.0));
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 0.98, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn dragged_faces_snap_to_the_ground_plane() {
+    let mut test = BoxFrameTestApp::new();
+    test.app.insert_resource(BoxFrameSnapping::default());
+    let a = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(0.0, -1.0, 0.0));
+
+    test.drag(Vec3::new(0.3, -0.5, 0.3), Vec3::new(0.3, -0.02, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn dragged_faces_snap_to_aabb_targets() {
+    let mut test = BoxFrameTestApp::new();
+    test.app.insert_resource(BoxFrameSnapping {
+        ground_plane: None,
+        ..default()
+    });
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.spawn((
+        SnapTarget::Aabb,
+        Aabb::from_min_max(Vec3::new(-0.5, -0.5, -0.5), Vec3::new(0.5, 0.5, 0.5)),
+        Transform::from_xyz(-2.0, 0.7, 0.0),
+    ));
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.18, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.2).abs() < 1e-4);
+}
+
+#[test]
+fn dragged_faces_snap_to_mesh_vertices() {
+    let mut test = BoxFrameTestApp::new();
+    test.app.insert_resource(BoxFrameSnapping {
+        ground_plane: None,
+        ..default()
+    });
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let mesh = test
+        .app
+        .world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .add(Cuboid::new(1.0, 2.5, 1.0));
+    test.spawn((
+        SnapTarget::Vertices,
+        Mesh3d(mesh),
+        Transform::from_xyz(-2.0, 0.0, 0.0),
+    ));
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.23, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.25).abs() < 1e-4);
+}
+
+#[test]
+fn dragged_faces_only_snap_within_the_threshold() {
+    let mut test = BoxFrameTestApp::new();
+    let settings = BoxFrameSnapping {
+        ground_plane: None,
+        ..default()
+    };
+    let threshold = settings.threshold_pixels;
+    test.app.insert_resource(settings);
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let _b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.5, 0.0));
+
+    // Drag to points 2 pixels inside and outside of the threshold.
+    let target = test.world_to_viewport(Vec3::new(0.3, 1.0, 0.3));
+    let pixels_per_unit = 10.0 * target.distance(test.world_to_viewport(Vec3::new(0.3, 0.9, 0.3)));
+    let inside = 1.0 - (threshold - 2.0) / pixels_per_unit;
+    let outside = 1.0 - (threshold + 2.0) / pixels_per_unit;
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, outside, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - outside).abs() < 1e-3);
+    test.drag(Vec3::new(0.3, outside, 0.3), Vec3::new(0.3, inside, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.0).abs() < 1e-4);
+}
+
+fn snap_indicator_count(test: &mut BoxFrameTestApp) -> usize {
+    let world = test.app.world_mut();
+    world
+        .query_filtered::<(), (With<PolylineHandle>, Without<ChildOf>)>()
+        .iter(world)
+        .count()
+}
+
+#[test]
+fn snap_indicators_are_drawn_while_faces_snap() {
+    let mut test = BoxFrameTestApp::new();
+    test.app.insert_resource(BoxFrameSnapping {
+        ground_plane: None,
+        ..default()
+    });
+    let _a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let _b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.5, 0.0));
+    assert_eq!(snap_indicator_count(&mut test), 0);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(0.3, 0.98, 0.3));
+    test.update();
+    assert_eq!(snap_indicator_count(&mut test), 1);
+
+    test.hover(Vec3::new(0.3, 1.5, 0.3));
+    test.update();
+    assert_eq!(snap_indicator_count(&mut test), 0);
+
+    test.hover(Vec3::new(0.3, 0.98, 0.3));
+    test.update();
+    assert_eq!(snap_indicator_count(&mut test), 1);
+    test.release(PointerButton::Primary);
+    test.update();
+    assert_eq!(snap_indicator_count(&mut test), 0);
+}
+
+#[test]
+fn removing_a_snapping_frame_despawns_its_snap_indicator() {
+    let mut test = BoxFrameTestApp::new();
+    test.app.insert_resource(BoxFrameSnapping {
+        ground_plane: None,
+        ..default()
+    });
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    let _b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(-2.0, 0.5, 0.0));
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    test.press(PointerButton::Primary);
+    test.hover(Vec3::new(0.3, 0.98, 0.3));
+    test.update();
+    assert_eq!(snap_indicator_count(&mut test), 1);
+
+    test.app.world_mut().entity_mut(a).remove::<BoxFrame>();
+    test.update();
+    assert_eq!(snap_indicator_count(&mut test), 0);
+}
+
+#[test]
+fn picking_follows_moved_and_replaced_frames() {
+    let mut test = BoxFrameTestApp::new();
+    for x in -5..5 {
+        for z in -5..5 {
+            if (x, z) != (0, 0) {
+                let position = Vec3::new(3.0 * x as f32, 0.0, 3.0 * z as f32);
+                test.app.world_mut().spawn((
+                    BoxFrame::new(UNIT_CUBE),
+                    Transform::from_translation(position),
+                ));
+            }
+        }
+    }
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.update();
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(test.pick().contains(&a));
+
+    test.app
+        .world_mut()
+        .entity_mut(a)
+        .insert(Transform::from_xyz(0.0, 1.5, 0.0));
+    test.update();
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(!test.pick().contains(&a));
+    test.hover(Vec3::new(0.3, 2.0, 0.3));
+    assert!(test.pick().contains(&a));
+
+    test.app.world_mut().despawn(a);
+    let b = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(0.0, 1.5, 0.0));
+    test.update();
+    test.hover(Vec3::new(0.3, 2.0, 0.3));
+    let picks = test.pick();
+    assert!(picks.contains(&b));
+    assert!(!picks.contains(&a));
+}
+
+fn batch_vertex_count(test: &BoxFrameTestApp) -> usize {
+    let batch = test.app.world().resource::<BoxFrameBatch>();
+    let meshes = test.app.world().resource::<Assets<Mesh>>();
+    meshes.get(&batch.mesh).unwrap().count_vertices()
+}
+
+fn has_children(test: &BoxFrameTestApp, frame: Entity) -> bool {
+    test.app
+        .world()
+        .get::<Children>(frame)
+        .is_some_and(|children| !children.is_empty())
+}
+
+#[test]
+fn batched_frames_are_promoted_while_hovered() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_batched(true),
+        Transform::default(),
+    ));
+    let b = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_batched(true),
+        Transform::from_xyz(-2.0, 0.0, 0.0),
+    ));
+    assert!(!has_children(&test, a));
+    assert!(!has_children(&test, b));
+    assert_eq!(batch_vertex_count(&test), 2 * 24);
+
+    test.hover(Vec3::new(0.3, 0.5, 0.3));
+    assert!(has_children(&test, a));
+    assert!(!has_children(&test, b));
+    assert_eq!(test.highlighted_faces(a), [POS_Y]);
+    assert_eq!(batch_vertex_count(&test), 24);
+
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.0).abs() < 1e-3);
+
+    test.hover(Vec3::new(0.0, 3.0, 0.0));
+    assert!(!has_children(&test, a));
+    assert_eq!(batch_vertex_count(&test), 2 * 24);
+}
+
+#[test]
+fn dragging_unbatched_frames_keeps_the_batch() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::default());
+    test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_batched(true),
+        Transform::from_xyz(-2.0, 0.0, 0.0),
+    ));
+    assert_eq!(batch_vertex_count(&test), 24);
+
+    // Clearing the mesh reveals whether it is rebuilt.
+    let mesh = test.app.world().resource::<BoxFrameBatch>().mesh.clone();
+    test.app
+        .world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .get_mut(&mesh)
+        .unwrap()
+        .insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
+    test.drag(Vec3::new(0.3, 0.5, 0.3), Vec3::new(0.3, 1.0, 0.3));
+    assert!((test.frame(a).faces()[POS_Y] - 1.0).abs() < 1e-3);
+    assert_eq!(batch_vertex_count(&test), 0);
+}
+
+fn translation(test: &BoxFrameTestApp, frame: Entity) -> Vec3 {
+    test.app
+        .world()
+        .get::<Transform>(frame)
+        .unwrap()
+        .translation
+}
+
+#[test]
+fn duplicated_frames_are_interactive() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::from_scale(Vec3::new(0.5, 1.0, 1.0)));
+    test.frame_mut(a).lock_face(POS_X);
+
+    let world = test.app.world_mut();
+    let mut commands = world.commands();
+    let mut source = commands.entity(a);
+    let in_place = source.duplicate_box_frame(None);
+    let adjacent = source.duplicate_box_frame(Some(POS_Y));
+    let mirrored = source.mirror_box_frame(NEG_X);
+    world.flush();
+    test.update();
+
+    assert_eq!(translation(&test, in_place), Vec3::ZERO);
+    assert_eq!(translation(&test, adjacent), Vec3::new(0.0, 1.0, 0.0));
+    // Offsets are along local axes, so they are scaled.
+    assert_eq!(translation(&test, mirrored), Vec3::new(-0.5, 0.0, 0.0));
+    assert!(test.frame(adjacent).is_face_locked(POS_X));
+    assert!(test.frame(mirrored).is_face_locked(NEG_X));
+    assert!(!test.frame(mirrored).is_face_locked(POS_X));
+    for copy in [in_place, adjacent, mirrored] {
+        assert_eq!(test.frame(copy).faces(), UNIT_CUBE);
+    }
+
+    // The copy on top of the source is hovered.
+    test.hover(Vec3::new(0.0, 1.5, 0.3));
+    assert_eq!(test.highlighted_faces(adjacent), [POS_Y]);
+    test.drag(Vec3::new(0.0, 1.5, 0.3), Vec3::new(0.0, 2.0, 0.3));
+    assert!((test.frame(adjacent).faces()[POS_Y] - 1.0).abs() < 1e-3);
+    assert_eq!(test.frame(a).faces(), UNIT_CUBE);
+}
+
+#[test]
+fn arrays_of_frames_are_spaced_by_extents() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(0.0, 0.0, -2.0));
+
+    let world = test.app.world_mut();
+    let mut commands = world.commands();
+    let mut source = commands.entity(a);
+    let row = source.array_box_frame(BoxFrameArray::linear(NEG_X, 2).with_gap(Vec3::splat(0.5)));
+    let grid = source.array_box_frame(BoxFrameArray::grid(UVec3::new(2, 2, 1)));
+    world.flush();
+    test.update();
+
+    let row: Vec<_> = row
+        .into_iter()
+        .map(|copy| translation(&test, copy))
+        .collect();
+    assert_eq!(
+        row,
+        [Vec3::new(-1.5, 0.0, -2.0), Vec3::new(-3.0, 0.0, -2.0)]
+    );
+    let grid: Vec<_> = grid
+        .into_iter()
+        .map(|copy| translation(&test, copy))
+        .collect();
+    assert_eq!(
+        grid,
+        [
+            Vec3::new(1.0, 0.0, -2.0),
+            Vec3::new(0.0, 1.0, -2.0),
+            Vec3::new(1.0, 1.0, -2.0)
+        ]
+    );
+}
+
+#[test]
+fn split_frames_merge_back_into_one() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(0.0, 0.0, -2.0));
+
+    let world = test.app.world_mut();
+    world
+        .commands()
+        .entity(a)
+        .split_box_frame(BoxFrameSplit::Every {
+            axis: 1,
+            interval: 0.3,
+        });
+    world.flush();
+    test.update();
+
+    let divided: Vec<_> = test
+        .app
+        .world_mut()
+        .resource_mut::<Events<BoxFrameDivided>>()
+        .drain()
+        .collect();
+    let [BoxFrameDivided { frame, parts }] = &divided[..] else {
+        panic!("{divided:?}");
+    };
+    assert_eq!(*frame, a);
+    assert_eq!(parts.len(), 4);
+    assert_eq!(parts[0], a);
+    let thicknesses: Vec<_> = parts
+        .iter()
+        .map(|&part| test.frame(part).extents()[1])
+        .collect();
+    for (thickness, expected) in thicknesses.iter().zip([0.3, 0.3, 0.3, 0.1]) {
+        assert!((thickness - expected).abs() < 1e-5, "{thicknesses:?}");
+    }
+
+    // Parts that leave a gap can't be merged.
+    let world = test.app.world_mut();
+    world.commands().entity(a).merge_box_frames([parts[2]]);
+    world.flush();
+    test.update();
+    assert!(test.app.world().get_entity(parts[2]).is_ok());
+
+    let world = test.app.world_mut();
+    world
+        .commands()
+        .entity(parts[2])
+        .merge_box_frames(parts.clone());
+    world.flush();
+    test.update();
+    let merged: Vec<_> = test
+        .app
+        .world_mut()
+        .resource_mut::<Events<BoxFramesMerged>>()
+        .drain()
+        .collect();
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].frame, parts[2]);
+    for &part in parts.iter().filter(|&&part| part != parts[2]) {
+        assert!(test.app.world().get_entity(part).is_err());
+    }
+    assert_eq!(test.frame(parts[2]).faces(), UNIT_CUBE);
+
+    // Merged frames are interactive.
+    test.hover(Vec3::new(0.3, 0.5, -1.7));
+    assert_eq!(test.highlighted_faces(parts[2]), [POS_Y]);
+}
+
+#[test]
+fn mirrored_frames_reflect_section_planes() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn((
+        BoxFrame::new([0.0, 0.0, 0.0, 2.0, 1.0, 1.0])
+            .with_slice(0, 0.5)
+            .with_slice(1, 0.25),
+        Transform::from_xyz(0.0, 0.0, -3.0),
+    ));
+
+    let world = test.app.world_mut();
+    let mirrored = world.commands().entity(a).mirror_box_frame(POS_X);
+    world.flush();
+    test.update();
+    assert_eq!(test.frame(mirrored).slices, [Some(1.5), Some(0.25), None]);
+}
+
+#[test]
+fn split_frames_keep_section_planes_in_their_parts() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn((
+        BoxFrame::new(UNIT_CUBE)
+            .with_slice(0, 0.2)
+            .with_slice(1, 0.1),
+        Transform::from_xyz(0.0, 0.0, -3.0),
+    ));
+
+    let world = test.app.world_mut();
+    world
+        .commands()
+        .entity(a)
+        .split_box_frame(BoxFrameSplit::At {
+            axis: 1,
+            coords: vec![0.0],
+        });
+    world.flush();
+    test.update();
+    let divided: Vec<_> = test
+        .app
+        .world_mut()
+        .resource_mut::<Events<BoxFrameDivided>>()
+        .drain()
+        .collect();
+    let parts = divided[0].parts.clone();
+    // Planes across the cut are split with the frame.
+    assert_eq!(test.frame(parts[0]).slices, [Some(0.2), None, None]);
+    assert_eq!(test.frame(parts[1]).slices, [Some(0.2), Some(0.1), None]);
+
+    // The merged frame keeps its own planes and takes the others' missing ones.
+    let world = test.app.world_mut();
+    world
+        .commands()
+        .entity(parts[0])
+        .merge_box_frames([parts[1]]);
+    world.flush();
+    test.update();
+    assert_eq!(test.frame(parts[0]).faces(), UNIT_CUBE);
+    assert_eq!(test.frame(parts[0]).slices, [Some(0.2), Some(0.1), None]);
+}
+
+#[test]
+fn merged_frames_take_section_planes_from_the_first_frame_with_one() {
+    let mut test = BoxFrameTestApp::new();
+    let a = test.spawn_frame(UNIT_CUBE, Transform::from_xyz(0.0, 0.0, -3.0));
+    let b = test.spawn((
+        BoxFrame::new(UNIT_CUBE).with_slice(1, 0.25),
+        Transform::from_xyz(0.0, 1.0, -3.0),
+    ));
+    let c = test.spawn((
+        BoxFrame::new(UNIT_CUBE)
+            .with_slice(1, 0.0)
+            .with_slice(2, 0.3),
+        Transform::from_xyz(0.0, 2.0, -3.0),
+    ));
+
+    let world = test.app.world_mut();
+    world.commands().entity(a).merge_box_frames([b, c]);
+    world.flush();
+    test.update();
+    assert_eq!(test.frame(a).faces(), [-0.5, -0.5, -0.5, 0.5, 2.5, 0.5]);
+    // Planes are moved into the coordinates of the merged frame.
+    assert_eq!(test.frame(a).slices, [None, Some(1.25), Some(0.3)]);
+}
+
+#[test]
+fn frames_split_into_grids_of_sub_boxes() {
+    let split = BoxFrameSplit::Grid(UVec3::new(2, 1, 3));
+    let parts = split.parts([0.0, 0.0, 0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(parts.len(), 6);
+    assert_eq!(parts[0], [0.0, 0.0, 0.0, 0.5, 2.0, 1.0]);
+    assert_eq!(parts[1], [0.5, 0.0, 0.0, 1.0, 2.0, 1.0]);
+    assert_eq!(parts[5], [0.5, 0.0, 2.0, 1.0, 2.0, 3.0]);
+
+    let split = BoxFrameSplit::At {
+        axis: 2,
+        coords: vec![2.5, -1.0, 1.0, 1.0],
+    };
+    let parts = split.parts([0.0, 0.0, 3.0, 1.0, 2.0, 0.0]);
+    assert_eq!(
+        parts,
+        [
+            [0.0, 0.0, 0.0, 1.0, 2.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0, 2.0, 2.5],
+            [0.0, 0.0, 2.5, 1.0, 2.0, 3.0]
+        ]
+    );
+}